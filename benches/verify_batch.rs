@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dverse_identity::{verify, verify_batch, KeyPair, PublicKey};
+
+fn build_items(count: usize) -> Vec<(PublicKey, Vec<u8>, Vec<u8>)> {
+    (0..count)
+        .map(|i| {
+            let keypair = KeyPair::generate().expect("Should generate keypair");
+            let message = format!("D-Verse event #{}", i).into_bytes();
+            let signature = keypair.sign(&message).expect("Should sign message");
+            (keypair.public_key, message, signature)
+        })
+        .collect()
+}
+
+fn bench_batch_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verification");
+
+    for count in [100usize, 1024] {
+        let items = build_items(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("individual", count), &items, |b, items| {
+            b.iter(|| {
+                items
+                    .iter()
+                    .map(|(public_key, message, signature)| verify(public_key, message, signature))
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("batch", count), &items, |b, items| b.iter(|| verify_batch(items)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_verification);
+criterion_main!(benches);
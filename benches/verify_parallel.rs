@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dverse_identity::{verify_parallel, KeyPair, PrivateKey, PublicKey};
+
+fn build_items(count: usize) -> Vec<(PublicKey, Vec<u8>, Vec<u8>)> {
+    (0..count)
+        .map(|i| {
+            let keypair = KeyPair::generate().expect("Should generate keypair");
+            let message = format!("D-Verse event #{}", i).into_bytes();
+            let signature = keypair.sign(&message).expect("Should sign message");
+            (keypair.public_key, message, signature)
+        })
+        .collect()
+}
+
+fn bench_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verification");
+
+    for count in [64usize, 1024] {
+        let items = build_items(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", count), &items, |b, items| {
+            b.iter(|| {
+                items
+                    .iter()
+                    .map(|(public_key, message, signature)| {
+                        let verifier = KeyPair {
+                            private_key: PrivateKey::from_bytes(vec![0; 32]),
+                            public_key: public_key.clone(),
+                        };
+                        verifier.verify(message, signature)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("parallel", count), &items, |b, items| {
+            b.iter(|| verify_parallel(items))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_verification);
+criterion_main!(benches);
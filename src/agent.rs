@@ -0,0 +1,97 @@
+// An ssh-agent style holder for unlocked identities.
+//
+// Applications hand their key pairs to the agent once and afterwards only refer to them by
+// DID. The agent never returns private key material, and can be locked with a passphrase
+// so that no signatures are produced until it is unlocked again.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::{Did, IdentityError, KeyPair, Result};
+
+#[derive(Default)]
+pub struct IdentityAgent {
+    identities: HashMap<Did, KeyPair>,
+    // SHA-256 of the passphrase while locked.
+    lock: Option<[u8; 32]>,
+}
+
+impl IdentityAgent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads `keypair` into the agent and returns the DID it can now sign for.
+    pub fn add_identity(&mut self, keypair: KeyPair) -> Result<Did> {
+        self.ensure_unlocked()?;
+        let did = Did::from_public_key(&keypair.public_key)?;
+        self.identities.insert(did.clone(), keypair);
+        Ok(did)
+    }
+
+    /// Unloads the identity, returning whether it was present.
+    pub fn remove_identity(&mut self, did: &Did) -> Result<bool> {
+        self.ensure_unlocked()?;
+        Ok(self.identities.remove(did).is_some())
+    }
+
+    /// DIDs of all loaded identities, sorted for stable output.
+    pub fn list_identities(&self) -> Vec<Did> {
+        let mut dids: Vec<Did> = self.identities.keys().cloned().collect();
+        dids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        dids
+    }
+
+    pub fn sign(&self, did: &Did, message: &[u8]) -> Result<Vec<u8>> {
+        self.ensure_unlocked()?;
+        let keypair = self
+            .identities
+            .get(did)
+            .ok_or_else(|| IdentityError::InvalidKey(format!("No identity loaded for {}", did)))?;
+        keypair.sign(message)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.lock.is_some()
+    }
+
+    /// Refuses all signing and identity changes until `unlock` is called with the same
+    /// passphrase.
+    pub fn lock(&mut self, passphrase: &str) -> Result<()> {
+        self.ensure_unlocked()?;
+        self.lock = Some(Sha256::digest(passphrase.as_bytes()).into());
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, passphrase: &str) -> Result<()> {
+        let expected = self
+            .lock
+            .ok_or_else(|| IdentityError::AgentLocked("Agent is not locked".to_string()))?;
+        let provided: [u8; 32] = Sha256::digest(passphrase.as_bytes()).into();
+        if !bool::from(expected.ct_eq(&provided)) {
+            return Err(IdentityError::AgentLocked("Incorrect passphrase".to_string()));
+        }
+        self.lock = None;
+        Ok(())
+    }
+
+    fn ensure_unlocked(&self) -> Result<()> {
+        if self.is_locked() {
+            return Err(IdentityError::AgentLocked("Agent is locked".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Only the DIDs are printed; the derived `Debug` would expose private key bytes.
+impl fmt::Debug for IdentityAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentityAgent")
+            .field("identities", &self.list_identities())
+            .field("locked", &self.is_locked())
+            .finish()
+    }
+}
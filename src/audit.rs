@@ -0,0 +1,91 @@
+// Tamper-evident, hash-linked audit log.
+//
+// Each record stores the SHA-256 hash of the previous record and a signature over
+// `prev_hash || entry`. Changing, removing or reordering any record breaks either a
+// signature or a hash link, which `verify_chain` detects.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{IdentityError, KeyPair, PublicKey, Result};
+
+const GENESIS_HASH: [u8; 32] = [0u8; 32];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub entry: Vec<u8>,
+    pub prev_hash: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl AuditRecord {
+    /// The hash the next record links to. Covers every field of this record.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(self.prev_hash)
+            .chain_update((self.entry.len() as u64).to_be_bytes())
+            .chain_update(&self.entry)
+            .chain_update(&self.signature)
+            .finalize()
+            .into()
+    }
+
+    fn signed_bytes(prev_hash: &[u8; 32], entry: &[u8]) -> Vec<u8> {
+        [prev_hash.as_slice(), entry].concat()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLog {
+    records: Vec<AuditRecord>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps previously persisted records. Call `verify_chain` before trusting them.
+    pub fn from_records(records: Vec<AuditRecord>) -> Self {
+        AuditLog { records }
+    }
+
+    pub fn records(&self) -> &[AuditRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn append(&mut self, keypair: &KeyPair, entry: &[u8]) -> Result<&AuditRecord> {
+        let prev_hash = self.records.last().map_or(GENESIS_HASH, AuditRecord::hash);
+        let signature = keypair.sign(&AuditRecord::signed_bytes(&prev_hash, entry))?;
+        self.records.push(AuditRecord { entry: entry.to_vec(), prev_hash, signature });
+        Ok(self.records.last().expect("record was just pushed"))
+    }
+
+    /// Walks the log from the first record, checking every hash link and signature.
+    pub fn verify_chain(&self, public_key: &PublicKey) -> Result<()> {
+        let mut expected_prev_hash = GENESIS_HASH;
+        for (index, record) in self.records.iter().enumerate() {
+            if record.prev_hash != expected_prev_hash {
+                return Err(IdentityError::SignatureError(format!(
+                    "Audit record {} does not link to its predecessor",
+                    index
+                )));
+            }
+            crate::verify_with_public_key(
+                public_key,
+                &AuditRecord::signed_bytes(&record.prev_hash, &record.entry),
+                &record.signature,
+            )?;
+            expected_prev_hash = record.hash();
+        }
+        Ok(())
+    }
+}
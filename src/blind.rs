@@ -0,0 +1,244 @@
+// Blind Schnorr signatures producing standard Ed25519 signatures.
+//
+// Ed25519 derives its nonce from the message, so it cannot sign a message it never sees.
+// Blind issuance therefore needs one extra round in which the signer commits to a fresh
+// random nonce. The flow is:
+//
+//   signer: (nonce, commitment) = keypair.blind_commit()          -> sends commitment
+//   user:   (blinded, unblinder) = blind(msg, pk, &commitment, &factor) -> sends blinded
+//   signer: blind_sig = keypair.sign_blinded(nonce, &blinded)     -> sends blind_sig
+//   user:   signature = unblind(&blind_sig, &unblinder)
+//
+// The resulting 64-byte signature verifies with the ordinary Ed25519 `verify`, and the
+// signer's transcript (commitment, blinded challenge, response) is statistically
+// independent of it, so the signer cannot link the signature to the session.
+//
+// Security note: blind Schnorr signatures are only secure when the signer completes
+// sessions one at a time. Running many sessions concurrently enables the ROS attack, which
+// lets a user forge one more signature than the signer issued. Issuers must serialize
+// sessions.
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{IdentityError, KeyPair, PublicKey, Result, Signature};
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    OsRng.fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn decompress_public_key(public_key: &PublicKey) -> Result<EdwardsPoint> {
+    let bytes: [u8; 32] = public_key
+        .as_bytes()
+        .try_into()
+        .map_err(|_| IdentityError::InvalidKey(format!("Public key must be 32 bytes, got {}", public_key.as_bytes().len())))?;
+    CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or_else(|| IdentityError::InvalidKey("Public key is not a valid Edwards point".to_string()))
+}
+
+fn canonical_scalar(bytes: &[u8; 32], what: &str) -> Result<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+        .ok_or_else(|| IdentityError::SignatureError(format!("{} is not a canonical scalar", what)))
+}
+
+/// The user's secret blinding values. Use a fresh factor for every signature. Wiped from
+/// memory when dropped.
+pub struct BlindingFactor {
+    alpha: Scalar,
+    beta: Scalar,
+}
+
+impl BlindingFactor {
+    pub fn random() -> Self {
+        BlindingFactor { alpha: random_scalar(), beta: random_scalar() }
+    }
+}
+
+/// The signer's secret nonce for one session. Consumed by `sign_blinded`, and wiped from
+/// memory when dropped.
+pub struct BlindSigningNonce {
+    r: Scalar,
+}
+
+/// The signer's public nonce commitment `R`, sent to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindCommitment {
+    point: EdwardsPoint,
+}
+
+/// The blinded challenge the user sends to the signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindedMessage {
+    challenge: Scalar,
+}
+
+/// The signer's response to a `BlindedMessage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindSignature {
+    response: Scalar,
+}
+
+/// What the user keeps between `blind` and `unblind`. The values linking the session to
+/// the final signature are wiped from memory when dropped.
+pub struct Unblinder {
+    alpha: Scalar,
+    blinded_commitment: EdwardsPoint,
+    signer_commitment: EdwardsPoint,
+    signer_key: EdwardsPoint,
+    challenge: Scalar,
+}
+
+impl Zeroize for BlindingFactor {
+    fn zeroize(&mut self) {
+        self.alpha.zeroize();
+        self.beta.zeroize();
+    }
+}
+
+impl Drop for BlindingFactor {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for BlindingFactor {}
+
+impl Zeroize for BlindSigningNonce {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+    }
+}
+
+impl Drop for BlindSigningNonce {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for BlindSigningNonce {}
+
+impl Zeroize for Unblinder {
+    fn zeroize(&mut self) {
+        self.alpha.zeroize();
+        self.blinded_commitment.zeroize();
+        self.challenge.zeroize();
+    }
+}
+
+impl Drop for Unblinder {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Unblinder {}
+
+impl BlindCommitment {
+    /// Rebuilds the signer's commitment. Points that do not decompress, or that have small
+    /// order (including the identity), are `InvalidKey`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        let point = CompressedEdwardsY(*bytes)
+            .decompress()
+            .ok_or_else(|| IdentityError::InvalidKey("Commitment is not a valid Edwards point".to_string()))?;
+        if point.is_small_order() {
+            return Err(IdentityError::InvalidKey("Commitment point has small order".to_string()));
+        }
+        Ok(BlindCommitment { point })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.point.compress().to_bytes()
+    }
+}
+
+impl BlindedMessage {
+    /// Rebuilds the user's blinded challenge. A non-canonical scalar is `SignatureError`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        Ok(BlindedMessage { challenge: canonical_scalar(bytes, "Blinded challenge")? })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.challenge.to_bytes()
+    }
+}
+
+impl BlindSignature {
+    /// Rebuilds the signer's response. A non-canonical scalar is `SignatureError`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        Ok(BlindSignature { response: canonical_scalar(bytes, "Blind signature response")? })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.response.to_bytes()
+    }
+}
+
+/// Blinds `message` for the signer identified by `signer_public_key`, given the signer's
+/// nonce commitment for this session.
+pub fn blind(
+    message: &[u8],
+    signer_public_key: &PublicKey,
+    commitment: &BlindCommitment,
+    factor: &BlindingFactor,
+) -> Result<(BlindedMessage, Unblinder)> {
+    let signer_key = decompress_public_key(signer_public_key)?;
+
+    // R' = R + alpha*G + beta*A and c' = H(R' || A || M), the Ed25519 challenge for R'.
+    let blinded_commitment = commitment.point + ED25519_BASEPOINT_POINT * factor.alpha + signer_key * factor.beta;
+    let hash: [u8; 64] = Sha512::new()
+        .chain_update(blinded_commitment.compress().as_bytes())
+        .chain_update(signer_public_key.as_bytes())
+        .chain_update(message)
+        .finalize()
+        .into();
+    let signature_challenge = Scalar::from_bytes_mod_order_wide(&hash);
+    let challenge = signature_challenge + factor.beta;
+
+    Ok((
+        BlindedMessage { challenge },
+        Unblinder {
+            alpha: factor.alpha,
+            blinded_commitment,
+            signer_commitment: commitment.point,
+            signer_key,
+            challenge,
+        },
+    ))
+}
+
+/// Checks the signer's response and turns it into a standard 64-byte Ed25519 signature.
+pub fn unblind(blind_signature: &BlindSignature, unblinder: &Unblinder) -> Result<Signature> {
+    let expected = unblinder.signer_commitment + unblinder.signer_key * unblinder.challenge;
+    if ED25519_BASEPOINT_POINT * blind_signature.response != expected {
+        return Err(IdentityError::SignatureError("Blind signature response is invalid".to_string()));
+    }
+
+    let response = blind_signature.response + unblinder.alpha;
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(unblinder.blinded_commitment.compress().as_bytes());
+    bytes[32..].copy_from_slice(response.as_bytes());
+    Signature::from_bytes(&bytes)
+}
+
+impl KeyPair {
+    /// Starts a blind signing session by committing to a fresh random nonce.
+    pub fn blind_commit(&self) -> (BlindSigningNonce, BlindCommitment) {
+        let r = random_scalar();
+        (BlindSigningNonce { r }, BlindCommitment { point: ED25519_BASEPOINT_POINT * r })
+    }
+
+    /// Answers a blinded challenge. The nonce is consumed so it can never be reused,
+    /// since answering two challenges with one nonce reveals the private key.
+    pub fn sign_blinded(&self, nonce: BlindSigningNonce, blinded: &BlindedMessage) -> Result<BlindSignature> {
+        let signing_key = crate::signing_key_from(&self.private_key)?;
+        let secret_scalar = signing_key.to_scalar();
+        Ok(BlindSignature { response: nonce.r + blinded.challenge * secret_scalar })
+    }
+}
@@ -0,0 +1,73 @@
+// Time source for time-dependent checks (nonce TTLs, capability expiry, rate limits).
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Supplies the current time. Time-aware APIs take a `Clock` so tests can control it.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// Supplies a monotonic reading for measuring intervals, such as rate limiter refills,
+/// which must not jump when the wall clock is adjusted.
+pub trait MonotonicClock {
+    fn instant(&self) -> Instant;
+}
+
+/// The real wall clock. Used by every API that does not take an explicit clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl MonotonicClock for SystemClock {
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A manually driven clock for deterministic tests. Clones share the same time, so a test
+/// can keep one handle and advance the clock it passed into an API. As a `MonotonicClock`
+/// it reads as a fixed `Instant` plus however far the time has moved past `start`.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<SystemTime>>,
+    start: SystemTime,
+    origin: Instant,
+}
+
+impl MockClock {
+    pub fn new(start: SystemTime) -> Self {
+        MockClock { now: Arc::new(Mutex::new(start)), start, origin: Instant::now() }
+    }
+
+    pub fn set(&self, time: SystemTime) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = time;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MonotonicClock for MockClock {
+    fn instant(&self) -> Instant {
+        self.origin + elapsed(self.start, self.now())
+    }
+}
+
+// Elapsed time between two readings. A clock that moved backwards counts as no time passing.
+pub(crate) fn elapsed(earlier: SystemTime, later: SystemTime) -> Duration {
+    later.duration_since(earlier).unwrap_or_default()
+}
@@ -0,0 +1,59 @@
+// Compact storage for large DID registries.
+//
+// A `did:dverse` string is around 60 characters plus the `String` header; the binary form
+// (`Did::to_binary`) is 34 bytes. The store keeps only the binary form and converts at the
+// boundary, so callers still work with `Did` values.
+
+use std::collections::HashSet;
+
+use crate::{Did, Result};
+
+/// A set of identities held in their 34-byte binary form. Any multibase encoding of the
+/// same key, and a dual-key DID, map to the same entry as the plain DID of its Ed25519 key,
+/// and identities come back out in canonical (Base58Btc) form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactDidStore {
+    entries: HashSet<[u8; 34]>,
+}
+
+impl CompactDidStore {
+    pub fn new() -> Self {
+        CompactDidStore::default()
+    }
+
+    /// Adds `did`. Returns false if the identity was already stored. DIDs without a
+    /// decodable Ed25519 key return the error from `Did::to_binary`.
+    pub fn insert(&mut self, did: &Did) -> Result<bool> {
+        Ok(self.entries.insert(did.to_binary()?))
+    }
+
+    pub fn contains(&self, did: &Did) -> Result<bool> {
+        Ok(self.entries.contains(&did.to_binary()?))
+    }
+
+    /// The stored identity matching `did`, in canonical form.
+    pub fn get(&self, did: &Did) -> Result<Option<Did>> {
+        self.entries.get(&did.to_binary()?).map(Did::from_binary).transpose()
+    }
+
+    /// Removes `did`. Returns false if it was not stored.
+    pub fn remove(&mut self, did: &Did) -> Result<bool> {
+        Ok(self.entries.remove(&did.to_binary()?))
+    }
+
+    /// Every stored identity in canonical form, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Did> + '_ {
+        // Entries only ever come from `Did::to_binary`, so they always convert back.
+        self.entries
+            .iter()
+            .map(|binary| Did::from_binary(binary).expect("Stored binary DIDs are always valid"))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,206 @@
+// Self-signed profile credentials in the W3C Verifiable Credentials shape
+// (https://www.w3.org/TR/vc-data-model/).
+//
+// The subject issues the credential about itself, so verification only needs the key
+// embedded in the subject DID.
+
+use std::io::{BufRead, Read};
+
+use multibase::Base;
+use serde::{Deserialize, Serialize};
+
+use crate::document::DVERSE_ED25519_SIGNATURE_2024;
+use crate::resolver::PRIMARY_KEY_FRAGMENT;
+use crate::signer::HardwareSigner;
+use crate::{Did, IdentityError, Result};
+
+pub const CREDENTIALS_CONTEXT_V1: &str = "https://www.w3.org/2018/credentials/v1";
+pub const PROFILE_CREDENTIAL_TYPE: &str = "ProfileCredential";
+// Same scheme as document self proofs: Ed25519 over this crate's compact JSON.
+const PROOF_TYPE: &str = DVERSE_ED25519_SIGNATURE_2024;
+
+/// The profile claims an identity publishes about itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Ways to reach the subject, e.g. `mailto:` or `https:` URIs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contact_endpoints: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSubject {
+    pub id: Did,
+    #[serde(flatten)]
+    pub profile: Profile,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub verification_method: String,
+    /// Base58btc multibase signature over the credential without its proof.
+    pub proof_value: String,
+}
+
+/// A profile ("business card") credential signed by its own subject. Fields serialize in
+/// declaration order, and the signature covers the compact JSON of every field but `proof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    pub issuer: Did,
+    pub credential_subject: ProfileSubject,
+    pub proof: CredentialProof,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnsignedCredential<'a> {
+    #[serde(rename = "@context")]
+    context: &'a [String],
+    #[serde(rename = "type")]
+    credential_type: &'a [String],
+    issuer: &'a Did,
+    credential_subject: &'a ProfileSubject,
+}
+
+fn signing_bytes(context: &[String], credential_type: &[String], issuer: &Did, subject: &ProfileSubject) -> Result<Vec<u8>> {
+    let unsigned = UnsignedCredential { context, credential_type, issuer, credential_subject: subject };
+    serde_json::to_vec(&unsigned).map_err(|e| IdentityError::EncodingError(e.to_string()))
+}
+
+impl ProfileCredential {
+    /// Issues `profile` as a credential about, and signed by, `signer`'s DID.
+    pub fn issue(signer: &(impl HardwareSigner + ?Sized), profile: Profile) -> Result<Self> {
+        let did = Did::from_public_key(&signer.public_key()?)?;
+        let context = vec![CREDENTIALS_CONTEXT_V1.to_string()];
+        let credential_type = vec!["VerifiableCredential".to_string(), PROFILE_CREDENTIAL_TYPE.to_string()];
+        let subject = ProfileSubject { id: did.clone(), profile };
+
+        let signature = signer.sign(&signing_bytes(&context, &credential_type, &did, &subject)?)?;
+        Ok(ProfileCredential {
+            context,
+            credential_type,
+            proof: CredentialProof {
+                proof_type: PROOF_TYPE.to_string(),
+                verification_method: format!("{}#{}", did, PRIMARY_KEY_FRAGMENT),
+                proof_value: multibase::encode(Base::Base58Btc, signature),
+            },
+            issuer: did,
+            credential_subject: subject,
+        })
+    }
+
+    /// Checks that the credential is self-issued and that the proof was made by the key in
+    /// the subject DID over the credential's current contents.
+    pub fn verify(&self) -> Result<()> {
+        if self.issuer != self.credential_subject.id {
+            return Err(IdentityError::InvalidCredential(format!(
+                "Profile credential for {} was issued by {}",
+                self.credential_subject.id, self.issuer
+            )));
+        }
+        if self.proof.proof_type != PROOF_TYPE {
+            return Err(IdentityError::InvalidCredential(format!("Unsupported proof type {}", self.proof.proof_type)));
+        }
+
+        let (base, signature) = crate::decode_multibase(&self.proof.proof_value)?;
+        if base != Base::Base58Btc {
+            return Err(IdentityError::UnsupportedMultibase(format!("Unsupported multibase: {:?}", base)));
+        }
+        let payload = signing_bytes(&self.context, &self.credential_type, &self.issuer, &self.credential_subject)?;
+        crate::verify_with_public_key(&self.issuer.to_public_key()?, &payload, &signature)
+    }
+
+    pub fn profile(&self) -> &Profile {
+        &self.credential_subject.profile
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| IdentityError::EncodingError(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| IdentityError::DecodingError(e.to_string()))
+    }
+}
+
+/// The longest line `verify_jsonl` reads, excluding the line ending. Profile credentials
+/// are a few hundred bytes, so anything near this is not a credential.
+pub const MAX_JSONL_LINE_BYTES: usize = 64 * 1024;
+
+// The next line without its `\n` or `\r\n` ending, reading at most `MAX_JSONL_LINE_BYTES`
+// of it. The rest of an oversized line is skipped so the following lines still parse.
+fn read_capped_line<R: BufRead>(reader: &mut R) -> Option<Result<String>> {
+    let mut line = Vec::new();
+    let limit = MAX_JSONL_LINE_BYTES as u64 + 1;
+    match reader.by_ref().take(limit).read_until(b'\n', &mut line) {
+        Ok(0) => return None,
+        Ok(_) => {}
+        Err(e) => return Some(Err(IdentityError::DecodingError(e.to_string()))),
+    }
+
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    } else if line.len() > MAX_JSONL_LINE_BYTES {
+        if let Err(e) = skip_line(reader) {
+            return Some(Err(IdentityError::DecodingError(e.to_string())));
+        }
+        return Some(Err(IdentityError::DecodingError(format!(
+            "Line is longer than {} bytes",
+            MAX_JSONL_LINE_BYTES
+        ))));
+    }
+    Some(String::from_utf8(line).map_err(|e| IdentityError::DecodingError(e.to_string())))
+}
+
+fn skip_line<R: BufRead>(reader: &mut R) -> std::io::Result<()> {
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        match buffer.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let len = buffer.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// Lazily verifies a JSON Lines stream of credentials, one per line, yielding the issuer
+/// DID or the error for each line. Blank lines are skipped. Only one line is held in memory
+/// at a time, and a line longer than `MAX_JSONL_LINE_BYTES` is a `DecodingError` without
+/// being buffered.
+pub fn verify_jsonl<R: BufRead>(mut reader: R) -> impl Iterator<Item = Result<Did>> {
+    std::iter::from_fn(move || loop {
+        match read_capped_line(&mut reader)? {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => {
+                return Some(ProfileCredential::from_json(&line).and_then(|credential| {
+                    credential.verify()?;
+                    Ok(credential.issuer)
+                }))
+            }
+            Err(e) => return Some(Err(e)),
+        }
+    })
+}
@@ -0,0 +1,629 @@
+// W3C DID Core documents (https://www.w3.org/TR/did-core/).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use multibase::Base;
+
+use crate::signer::HardwareSigner;
+use crate::x25519::KeyAgreementPublicKey;
+use crate::{Did, IdentityError, KeyType, PublicKey, Result};
+
+pub const DID_CONTEXT_V1: &str = "https://www.w3.org/ns/did/v1";
+pub const X25519_KEY_AGREEMENT_KEY_2020: &str = "X25519KeyAgreementKey2020";
+/// The type of proofs made by `DidDocument::with_self_proof` and profile credentials: an
+/// Ed25519 signature over this crate's canonical JSON, not the W3C Data Integrity
+/// `Ed25519Signature2020` suite.
+pub const DVERSE_ED25519_SIGNATURE_2024: &str = "DverseEd25519Signature2024";
+
+// --- Document Types ---
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: String,
+    pub controller: String,
+    pub public_key_multibase: String,
+    /// Members this type does not model, kept as parsed so they survive a round trip.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub service_endpoint: String,
+    /// Members this type does not model, kept as parsed so they survive a round trip.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// A proof over a DID Document made by one of the document's own keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    /// The id of the signing verification method, e.g. `did:dverse:z...#keys-1`.
+    pub verification_method: String,
+    /// Base58btc multibase signature over the document without its proof.
+    pub proof_value: String,
+    /// Members this type does not model, kept as parsed so they survive a round trip.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// A DID Document. Serialization is deterministic: fields are always emitted in the
+/// declaration order below (`@context`, `id`, `alsoKnownAs`, `verificationMethod`,
+/// `authentication`, `assertionMethod`, `keyAgreement`, `service`, any unmodelled members in
+/// key order, `proof`), `@context` always starts with the DID v1 context, and
+/// list entries keep their insertion order. Building the same document twice therefore
+/// produces byte-identical JSON, which document proofs rely on. Do not reorder fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    /// Other identifiers for the same subject, e.g. a `did:web` alias.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub also_known_as: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_method: Vec<VerificationMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authentication: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_method: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_agreement: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service: Vec<Service>,
+    /// Members this type does not model (e.g. `controller`), kept as parsed and emitted in
+    /// key order after `service`, so a self proof covers them too.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<DocumentProof>,
+}
+
+// --- Builder ---
+#[derive(Debug, Clone)]
+pub struct DidDocumentBuilder {
+    document: DidDocument,
+}
+
+impl DidDocumentBuilder {
+    pub fn new(did: &Did) -> Self {
+        DidDocumentBuilder {
+            document: DidDocument {
+                context: vec![DID_CONTEXT_V1.to_string()],
+                id: did.as_str().to_string(),
+                also_known_as: Vec::new(),
+                verification_method: Vec::new(),
+                authentication: Vec::new(),
+                assertion_method: Vec::new(),
+                key_agreement: Vec::new(),
+                service: Vec::new(),
+                extra: BTreeMap::new(),
+                proof: None,
+            },
+        }
+    }
+
+    pub fn also_known_as(mut self, identifiers: Vec<String>) -> Self {
+        self.document.also_known_as.extend(identifiers);
+        self
+    }
+
+    pub fn verification_method(mut self, method: VerificationMethod) -> Self {
+        self.document.verification_method.push(method);
+        self
+    }
+
+    pub fn authentication(mut self, method_id: impl Into<String>) -> Self {
+        self.document.authentication.push(method_id.into());
+        self
+    }
+
+    pub fn assertion_method(mut self, method_id: impl Into<String>) -> Self {
+        self.document.assertion_method.push(method_id.into());
+        self
+    }
+
+    pub fn key_agreement(mut self, method_id: impl Into<String>) -> Self {
+        self.document.key_agreement.push(method_id.into());
+        self
+    }
+
+    /// Adds `key` as an `X25519KeyAgreementKey2020` verification method with id `method_id`
+    /// and lists it under `keyAgreement`, so peers can find the identity's encryption key.
+    pub fn key_agreement_key(mut self, method_id: impl Into<String>, controller: &Did, key: &KeyAgreementPublicKey) -> Self {
+        let method_id = method_id.into();
+        self.document.verification_method.push(VerificationMethod {
+            id: method_id.clone(),
+            method_type: X25519_KEY_AGREEMENT_KEY_2020.to_string(),
+            controller: controller.to_string(),
+            public_key_multibase: key.to_multibase(),
+            extra: BTreeMap::new(),
+        });
+        self.key_agreement(method_id)
+    }
+
+    pub fn service(mut self, service: Service) -> Self {
+        self.document.service.push(service);
+        self
+    }
+
+    pub fn build(self) -> DidDocument {
+        self.document
+    }
+}
+
+impl DidDocument {
+    /// Compact JSON in the fixed field order documented on `DidDocument`.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| IdentityError::EncodingError(e.to_string()))
+    }
+
+    pub fn to_json_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| IdentityError::EncodingError(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| IdentityError::DecodingError(e.to_string()))
+    }
+
+    /// Indented JSON for logs and debugging. Not the canonical form; sign `to_json_bytes`.
+    pub fn to_string_pretty(&self) -> String {
+        // Only strings and lists of plain structs, so serialization cannot fail.
+        serde_json::to_string_pretty(self).expect("DID documents always serialize to JSON")
+    }
+}
+
+impl std::fmt::Display for DidDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_string_pretty())
+    }
+}
+
+// --- Diff / Patch ---
+/// The changes needed to turn one version of a DID Document into another. Entries are
+/// matched by `id`: a changed verification method or service is carried whole in
+/// `replaced_*` and swapped in place. A changed `@context` or set of unmodelled members is
+/// carried whole in `context` or `extra`. When the target lists entries in a different
+/// order than applying the changes would give, `order` records the target order, so
+/// applying `a.diff(&b)` to `a` reproduces `b` apart from its proof. Proofs are never part
+/// of a patch: the patched document is unsigned and must be signed again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentPatch {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<String>>,
+    #[serde(default)]
+    pub added_also_known_as: Vec<String>,
+    #[serde(default)]
+    pub removed_also_known_as: Vec<String>,
+    pub added_verification_methods: Vec<VerificationMethod>,
+    pub removed_verification_methods: Vec<String>,
+    #[serde(default)]
+    pub replaced_verification_methods: Vec<VerificationMethod>,
+    pub added_authentication: Vec<String>,
+    pub removed_authentication: Vec<String>,
+    pub added_assertion_methods: Vec<String>,
+    pub removed_assertion_methods: Vec<String>,
+    #[serde(default)]
+    pub added_key_agreement: Vec<String>,
+    #[serde(default)]
+    pub removed_key_agreement: Vec<String>,
+    pub added_services: Vec<Service>,
+    pub removed_services: Vec<String>,
+    #[serde(default)]
+    pub replaced_services: Vec<Service>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<BTreeMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<DocumentOrder>,
+}
+
+/// The ids of every list in a DID Document, in document order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentOrder {
+    pub also_known_as: Vec<String>,
+    pub verification_method: Vec<String>,
+    pub authentication: Vec<String>,
+    pub assertion_method: Vec<String>,
+    pub key_agreement: Vec<String>,
+    pub service: Vec<String>,
+}
+
+impl DocumentOrder {
+    fn of(document: &DidDocument) -> Self {
+        DocumentOrder {
+            also_known_as: document.also_known_as.clone(),
+            verification_method: document.verification_method.iter().map(|m| m.id.clone()).collect(),
+            authentication: document.authentication.clone(),
+            assertion_method: document.assertion_method.clone(),
+            key_agreement: document.key_agreement.clone(),
+            service: document.service.iter().map(|s| s.id.clone()).collect(),
+        }
+    }
+}
+
+impl DocumentPatch {
+    pub fn is_empty(&self) -> bool {
+        self.context.is_none()
+            && self.added_also_known_as.is_empty()
+            && self.removed_also_known_as.is_empty()
+            && self.added_verification_methods.is_empty()
+            && self.removed_verification_methods.is_empty()
+            && self.replaced_verification_methods.is_empty()
+            && self.added_authentication.is_empty()
+            && self.removed_authentication.is_empty()
+            && self.added_assertion_methods.is_empty()
+            && self.removed_assertion_methods.is_empty()
+            && self.added_key_agreement.is_empty()
+            && self.removed_key_agreement.is_empty()
+            && self.added_services.is_empty()
+            && self.removed_services.is_empty()
+            && self.replaced_services.is_empty()
+            && self.extra.is_none()
+            && self.order.is_none()
+    }
+}
+
+/// Splits the changes from `from` to `to` into (added, removed ids, replaced).
+fn diff_by_id<T: Clone + PartialEq>(from: &[T], to: &[T], id: impl Fn(&T) -> &str) -> (Vec<T>, Vec<String>, Vec<T>) {
+    let find = |items: &[T], wanted: &str| items.iter().position(|item| id(item) == wanted);
+    let removed = from
+        .iter()
+        .filter(|old| find(to, id(old)).is_none())
+        .map(|old| id(old).to_string())
+        .collect();
+    let added = to.iter().filter(|new| find(from, id(new)).is_none()).cloned().collect();
+    let replaced = to
+        .iter()
+        .filter(|new| find(from, id(new)).is_some_and(|index| &from[index] != *new))
+        .cloned()
+        .collect();
+    (added, removed, replaced)
+}
+
+fn apply_by_id<T: Clone>(
+    items: &[T],
+    added: &[T],
+    removed: &[String],
+    replaced: &[T],
+    id: impl Fn(&T) -> &str,
+    kind: &str,
+) -> Result<Vec<T>> {
+    for removed_id in removed {
+        if !items.iter().any(|item| id(item) == removed_id) {
+            return Err(IdentityError::InvalidDocument(format!("Cannot remove missing {} {}", kind, removed_id)));
+        }
+    }
+    let mut result: Vec<T> = items
+        .iter()
+        .filter(|item| !removed.iter().any(|removed_id| removed_id == id(item)))
+        .cloned()
+        .collect();
+    for item in replaced {
+        match result.iter_mut().find(|existing| id(existing) == id(item)) {
+            Some(existing) => *existing = item.clone(),
+            None => {
+                return Err(IdentityError::InvalidDocument(format!("Cannot replace missing {} {}", kind, id(item))));
+            }
+        }
+    }
+    for item in added {
+        if result.iter().any(|existing| id(existing) == id(item)) {
+            return Err(IdentityError::InvalidDocument(format!("Cannot add duplicate {} {}", kind, id(item))));
+        }
+        result.push(item.clone());
+    }
+    Ok(result)
+}
+
+/// Puts `items` into the order given by `order`, which must name each entry exactly once.
+fn reorder_by_id<T>(mut items: Vec<T>, order: &[String], id: impl Fn(&T) -> &str, kind: &str) -> Result<Vec<T>> {
+    let mut result = Vec::with_capacity(items.len());
+    for wanted in order {
+        let index = items.iter().position(|item| id(item) == wanted).ok_or_else(|| {
+            IdentityError::InvalidDocument(format!("Patch order names unknown or repeated {} {}", kind, wanted))
+        })?;
+        result.push(items.remove(index));
+    }
+    if let Some(missing) = items.first() {
+        return Err(IdentityError::InvalidDocument(format!("Patch order is missing {} {}", kind, id(missing))));
+    }
+    Ok(result)
+}
+
+impl DidDocument {
+    /// Computes the patch that turns `self` into `other`. Both documents must describe the
+    /// same DID. `other`'s proof is not carried over.
+    pub fn diff(&self, other: &DidDocument) -> DocumentPatch {
+        let (added_also_known_as, removed_also_known_as, _) =
+            diff_by_id(&self.also_known_as, &other.also_known_as, |r| r);
+        let (added_verification_methods, removed_verification_methods, replaced_verification_methods) =
+            diff_by_id(&self.verification_method, &other.verification_method, |m| &m.id);
+        let (added_authentication, removed_authentication, _) =
+            diff_by_id(&self.authentication, &other.authentication, |r| r);
+        let (added_assertion_methods, removed_assertion_methods, _) =
+            diff_by_id(&self.assertion_method, &other.assertion_method, |r| r);
+        let (added_key_agreement, removed_key_agreement, _) =
+            diff_by_id(&self.key_agreement, &other.key_agreement, |r| r);
+        let (added_services, removed_services, replaced_services) = diff_by_id(&self.service, &other.service, |s| &s.id);
+
+        let mut patch = DocumentPatch {
+            id: other.id.clone(),
+            context: (self.context != other.context).then(|| other.context.clone()),
+            added_also_known_as,
+            removed_also_known_as,
+            added_verification_methods,
+            removed_verification_methods,
+            replaced_verification_methods,
+            added_authentication,
+            removed_authentication,
+            added_assertion_methods,
+            removed_assertion_methods,
+            added_key_agreement,
+            removed_key_agreement,
+            added_services,
+            removed_services,
+            replaced_services,
+            extra: (self.extra != other.extra).then(|| other.extra.clone()),
+            order: None,
+        };
+        // Only record the order when the changes alone would not reproduce it.
+        let target_order = DocumentOrder::of(other);
+        let reproduces_order = self
+            .apply_patch(&patch)
+            .is_ok_and(|patched| DocumentOrder::of(&patched) == target_order);
+        if !reproduces_order {
+            patch.order = Some(target_order);
+        }
+        patch
+    }
+
+    /// Applies `patch`, returning the updated document. Replaced entries keep their
+    /// position, added entries are appended, and if the patch records an order every list
+    /// is then rearranged to match it. The result is always unsigned: any proof is dropped,
+    /// since it no longer covers the patched contents.
+    pub fn apply_patch(&self, patch: &DocumentPatch) -> Result<DidDocument> {
+        if patch.id != self.id {
+            return Err(IdentityError::InvalidDocument(format!(
+                "Patch for {} cannot be applied to {}",
+                patch.id, self.id
+            )));
+        }
+
+        let mut document = DidDocument {
+            context: patch.context.clone().unwrap_or_else(|| self.context.clone()),
+            id: self.id.clone(),
+            also_known_as: apply_by_id(
+                &self.also_known_as,
+                &patch.added_also_known_as,
+                &patch.removed_also_known_as,
+                &[],
+                |r| r,
+                "alsoKnownAs entry",
+            )?,
+            verification_method: apply_by_id(
+                &self.verification_method,
+                &patch.added_verification_methods,
+                &patch.removed_verification_methods,
+                &patch.replaced_verification_methods,
+                |m| &m.id,
+                "verification method",
+            )?,
+            authentication: apply_by_id(
+                &self.authentication,
+                &patch.added_authentication,
+                &patch.removed_authentication,
+                &[],
+                |r| r,
+                "authentication reference",
+            )?,
+            assertion_method: apply_by_id(
+                &self.assertion_method,
+                &patch.added_assertion_methods,
+                &patch.removed_assertion_methods,
+                &[],
+                |r| r,
+                "assertion method reference",
+            )?,
+            key_agreement: apply_by_id(
+                &self.key_agreement,
+                &patch.added_key_agreement,
+                &patch.removed_key_agreement,
+                &[],
+                |r| r,
+                "key agreement reference",
+            )?,
+            service: apply_by_id(
+                &self.service,
+                &patch.added_services,
+                &patch.removed_services,
+                &patch.replaced_services,
+                |s| &s.id,
+                "service",
+            )?,
+            extra: patch.extra.clone().unwrap_or_else(|| self.extra.clone()),
+            proof: None,
+        };
+
+        if let Some(order) = &patch.order {
+            document.also_known_as =
+                reorder_by_id(document.also_known_as, &order.also_known_as, |r| r, "alsoKnownAs entry")?;
+            document.verification_method = reorder_by_id(
+                document.verification_method,
+                &order.verification_method,
+                |m| &m.id,
+                "verification method",
+            )?;
+            document.authentication =
+                reorder_by_id(document.authentication, &order.authentication, |r| r, "authentication reference")?;
+            document.assertion_method = reorder_by_id(
+                document.assertion_method,
+                &order.assertion_method,
+                |r| r,
+                "assertion method reference",
+            )?;
+            document.key_agreement =
+                reorder_by_id(document.key_agreement, &order.key_agreement, |r| r, "key agreement reference")?;
+            document.service = reorder_by_id(document.service, &order.service, |s| &s.id, "service")?;
+        }
+        Ok(document)
+    }
+}
+
+// --- Key Usage ---
+/// A DID Core verification relationship a key can be authorized for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyUsage {
+    /// Listed in `authentication`: proving control of the DID, e.g. logging in.
+    Authentication,
+    /// Listed in `assertionMethod`: issuing statements such as credentials.
+    AssertionMethod,
+}
+
+impl DidDocument {
+    /// The verification relationships `public_key` is authorized for in this document.
+    pub fn key_usages(&self, public_key: &PublicKey) -> Vec<KeyUsage> {
+        let method_ids: Vec<&str> = self
+            .verification_method
+            .iter()
+            .filter(|method| {
+                Did::decode_key_material(&method.public_key_multibase)
+                    .map(|decoded| &decoded.public_key == public_key)
+                    .unwrap_or(false)
+            })
+            .map(|method| method.id.as_str())
+            .collect();
+
+        let mut usages = Vec::new();
+        if self.authentication.iter().any(|id| method_ids.contains(&id.as_str())) {
+            usages.push(KeyUsage::Authentication);
+        }
+        if self.assertion_method.iter().any(|id| method_ids.contains(&id.as_str())) {
+            usages.push(KeyUsage::AssertionMethod);
+        }
+        usages
+    }
+
+    /// Verifies `signature` only if this document authorizes `public_key` for
+    /// `required_usage`, so e.g. an authentication-only key cannot sign assertions.
+    pub fn verify_with_usage(
+        &self,
+        public_key: &PublicKey,
+        message: &[u8],
+        signature: &[u8],
+        required_usage: KeyUsage,
+    ) -> Result<()> {
+        if !self.key_usages(public_key).contains(&required_usage) {
+            return Err(IdentityError::InvalidKey(format!(
+                "Key is not authorized for {:?} in {}",
+                required_usage, self.id
+            )));
+        }
+        crate::verify_with_public_key(public_key, message, signature)
+    }
+}
+
+// --- Key Agreement ---
+impl DidDocument {
+    /// The X25519 keys listed under `keyAgreement`, in document order. Each reference must
+    /// point to an `X25519KeyAgreementKey2020` verification method in this document.
+    pub fn key_agreement_keys(&self) -> Result<Vec<KeyAgreementPublicKey>> {
+        self.key_agreement
+            .iter()
+            .map(|method_id| {
+                let method = self.verification_method.iter().find(|m| &m.id == method_id).ok_or_else(|| {
+                    IdentityError::InvalidDocument(format!("Key agreement method {} is not defined in the document", method_id))
+                })?;
+                if method.method_type != X25519_KEY_AGREEMENT_KEY_2020 {
+                    return Err(IdentityError::InvalidDocument(format!(
+                        "Key agreement method {} has type {}, expected {}",
+                        method_id, method.method_type, X25519_KEY_AGREEMENT_KEY_2020
+                    )));
+                }
+                KeyAgreementPublicKey::from_multibase(&method.public_key_multibase)
+            })
+            .collect()
+    }
+}
+
+// --- Self Proofs ---
+impl DidDocument {
+    // The signed form: the canonical JSON of the document without its proof.
+    fn unsigned_bytes(&self) -> Result<Vec<u8>> {
+        DidDocument { proof: None, ..self.clone() }.to_json_bytes()
+    }
+
+    // The Ed25519 key of `method_id`, which must also be listed in `authentication` or
+    // `assertionMethod`: a key the document only declares is not authorized to sign for it.
+    fn verification_method_key(&self, method_id: &str) -> Result<PublicKey> {
+        if !self.authentication.iter().chain(&self.assertion_method).any(|id| id == method_id) {
+            return Err(IdentityError::InvalidDocument(format!(
+                "Proof method {} is not listed in authentication or assertionMethod",
+                method_id
+            )));
+        }
+        let method = self.verification_method.iter().find(|m| m.id == method_id).ok_or_else(|| {
+            IdentityError::InvalidDocument(format!("Proof method {} is not defined in {}", method_id, self.id))
+        })?;
+        let decoded = Did::decode_key_material(&method.public_key_multibase)?;
+        if decoded.key_type != KeyType::Ed25519 {
+            return Err(IdentityError::InvalidDocument(format!("Proof method {} is not an Ed25519 key", method_id)));
+        }
+        Ok(decoded.public_key)
+    }
+
+    /// Returns a copy of this document carrying a proof by `signer`, which must hold the
+    /// key of the verification method `method_id` in this document. The method must be
+    /// listed in `authentication` or `assertionMethod`. Any existing proof is replaced.
+    pub fn with_self_proof(&self, signer: &(impl HardwareSigner + ?Sized), method_id: &str) -> Result<DidDocument> {
+        if self.verification_method_key(method_id)? != signer.public_key()? {
+            return Err(IdentityError::InvalidKey(format!("Signer does not hold the key of {}", method_id)));
+        }
+        let signature = signer.sign(&self.unsigned_bytes()?)?;
+        Ok(DidDocument {
+            proof: Some(DocumentProof {
+                proof_type: DVERSE_ED25519_SIGNATURE_2024.to_string(),
+                verification_method: method_id.to_string(),
+                proof_value: multibase::encode(Base::Base58Btc, signature),
+                extra: BTreeMap::new(),
+            }),
+            ..self.clone()
+        })
+    }
+
+    /// Checks the document's proof against the referenced verification method in this same
+    /// document, over the canonical JSON of everything but the proof. A missing proof, or
+    /// one naming a method the document does not define or does not list in
+    /// `authentication` or `assertionMethod`, is `InvalidDocument`.
+    pub fn verify_self_proof(&self) -> Result<()> {
+        let proof = self
+            .proof
+            .as_ref()
+            .ok_or_else(|| IdentityError::InvalidDocument(format!("{} has no proof", self.id)))?;
+        if proof.proof_type != DVERSE_ED25519_SIGNATURE_2024 {
+            return Err(IdentityError::InvalidDocument(format!("Unsupported proof type {}", proof.proof_type)));
+        }
+        let public_key = self.verification_method_key(&proof.verification_method)?;
+
+        let (base, signature) = crate::decode_multibase(&proof.proof_value)?;
+        if base != Base::Base58Btc {
+            return Err(IdentityError::UnsupportedMultibase(format!("Unsupported multibase: {:?}", base)));
+        }
+        crate::verify_with_public_key(&public_key, &self.unsigned_bytes()?, &signature)
+    }
+}
@@ -0,0 +1,107 @@
+// SLIP-0010 extended keys for Ed25519 (https://github.com/satoshilabs/slips/blob/master/slip-0010.md).
+//
+// Ed25519 only supports hardened derivation, so every child index is hardened.
+
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::{IdentityError, KeyPair, PrivateKey, PublicKey, Result};
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const MASTER_KEY_HMAC_KEY: &[u8] = b"ed25519 seed";
+
+/// A key pair together with the chain code wallets need to derive child keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedKeyPair {
+    keypair: KeyPair,
+    chain_code: [u8; 32],
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> Result<Zeroizing<[u8; 64]>> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).map_err(|e| IdentityError::KeyGenerationError(e.to_string()))?;
+    for part in parts {
+        mac.update(part);
+    }
+    let mut output = Zeroizing::new([0u8; 64]);
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    Ok(output)
+}
+
+fn split(output: &[u8; 64]) -> ExtendedKeyPair {
+    let mut secret = Zeroizing::new([0u8; 32]);
+    secret.copy_from_slice(&output[..32]);
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&output[32..]);
+
+    let signing_key = SigningKey::from_bytes(&secret);
+    ExtendedKeyPair {
+        keypair: KeyPair {
+            private_key: PrivateKey::from_bytes(secret.to_vec()),
+            public_key: PublicKey::from_bytes(signing_key.verifying_key().to_bytes().to_vec()),
+        },
+        chain_code,
+    }
+}
+
+impl ExtendedKeyPair {
+    /// The SLIP-0010 master key for `seed` (16 to 64 bytes, e.g. a BIP39 seed).
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(IdentityError::InvalidArgument(format!("Seed must be 16 to 64 bytes, got {}", seed.len())));
+        }
+        Ok(split(&*hmac_sha512(MASTER_KEY_HMAC_KEY, &[seed])?))
+    }
+
+    pub fn keypair(&self) -> &KeyPair {
+        &self.keypair
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn into_keypair(self) -> KeyPair {
+        self.keypair
+    }
+
+    /// Derives the hardened child at `index` (0-based, below 2^31). The result is the
+    /// same as SLIP-0010 path component `index'`.
+    pub fn derive_extended(&self, index: u32) -> Result<ExtendedKeyPair> {
+        if index >= HARDENED_OFFSET {
+            return Err(IdentityError::InvalidArgument(format!(
+                "Child index must be below 2^31 (it is hardened automatically), got {}",
+                index
+            )));
+        }
+        self.derive_child(index | HARDENED_OFFSET)
+    }
+
+    /// Derives the child at the raw SLIP-0010 `index`, which must already be hardened
+    /// (2^31 or above, e.g. `0x8000_0000` for `0'`). Ed25519 has no non-hardened
+    /// derivation, so smaller indices are rejected rather than silently hardened.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedKeyPair> {
+        if index < HARDENED_OFFSET {
+            return Err(IdentityError::InvalidArgument(format!(
+                "Ed25519 only supports hardened derivation; child index must be at least 2^31, got {}",
+                index
+            )));
+        }
+        let output = hmac_sha512(&self.chain_code, &[&[0u8], self.keypair.private_key.as_bytes(), &index.to_be_bytes()])?;
+        Ok(split(&output))
+    }
+}
+
+impl KeyPair {
+    /// Pairs this key with `chain_code` so wallet-style children can be derived from it.
+    pub fn to_extended(&self, chain_code: [u8; 32]) -> Result<ExtendedKeyPair> {
+        if self.private_key.as_bytes().len() != 32 {
+            return Err(IdentityError::InvalidKey(format!(
+                "Private key must be 32 bytes, got {}",
+                self.private_key.as_bytes().len()
+            )));
+        }
+        Ok(ExtendedKeyPair { keypair: self.clone(), chain_code })
+    }
+}
@@ -0,0 +1,658 @@
+// FROST threshold signing for Ed25519 (RFC 9591, FROST(Ed25519, SHA-512)).
+//
+// A group of `max_signers` participants runs a three-part distributed key generation
+// (Pedersen DKG with proofs of knowledge), after which any `min_signers` of them can
+// cooperate to produce a standard 64-byte Ed25519 signature for the group public key.
+// No single participant ever holds the group secret.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{IdentityError, PublicKey, Result};
+
+const CONTEXT_STRING: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+// --- Hashing ---
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+fn h1(m: &[u8]) -> Scalar {
+    hash_to_scalar(&[CONTEXT_STRING, b"rho", m])
+}
+
+// H2 is the plain Ed25519 challenge hash so that aggregated signatures verify as Ed25519.
+fn h2(m: &[u8]) -> Scalar {
+    hash_to_scalar(&[m])
+}
+
+fn h3(m: &[u8]) -> Scalar {
+    hash_to_scalar(&[CONTEXT_STRING, b"nonce", m])
+}
+
+fn h4(m: &[u8]) -> [u8; 64] {
+    Sha512::new().chain_update(CONTEXT_STRING).chain_update(b"msg").chain_update(m).finalize().into()
+}
+
+fn h5(m: &[u8]) -> [u8; 64] {
+    Sha512::new().chain_update(CONTEXT_STRING).chain_update(b"com").chain_update(m).finalize().into()
+}
+
+fn hdkg(m: &[u8]) -> Scalar {
+    hash_to_scalar(&[CONTEXT_STRING, b"dkg", m])
+}
+
+// --- Scalar/Point Helpers ---
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    OsRng.fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn identifier_scalar(identifier: u16) -> Scalar {
+    Scalar::from(identifier as u64)
+}
+
+fn decompress(bytes: &[u8; 32]) -> Result<EdwardsPoint> {
+    CompressedEdwardsY(*bytes)
+        .decompress()
+        .ok_or_else(|| IdentityError::InvalidKey("Bytes are not a valid Edwards point".to_string()))
+}
+
+fn canonical_scalar(bytes: &[u8; 32]) -> Result<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+        .ok_or_else(|| IdentityError::InvalidKey("Scalar is not canonical".to_string()))
+}
+
+fn validate_identifier(identifier: u16, max_signers: u16) -> Result<()> {
+    if identifier == 0 || identifier > max_signers {
+        return Err(IdentityError::KeyGenerationError(format!(
+            "Participant identifier {} must be in 1..={}",
+            identifier, max_signers
+        )));
+    }
+    Ok(())
+}
+
+fn evaluate_polynomial(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients.iter().rev().fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient)
+}
+
+fn evaluate_commitment(commitments: &[EdwardsPoint], x: Scalar) -> EdwardsPoint {
+    commitments.iter().rev().fold(EdwardsPoint::default(), |acc, commitment| acc * x + commitment)
+}
+
+fn lagrange_coefficient(identifier: u16, signers: &BTreeSet<u16>) -> Scalar {
+    let x_i = identifier_scalar(identifier);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for &other in signers.iter().filter(|&&other| other != identifier) {
+        let x_j = identifier_scalar(other);
+        numerator *= x_j;
+        denominator *= x_j - x_i;
+    }
+    numerator * denominator.invert()
+}
+
+// --- Distributed Key Generation ---
+/// Secret state a participant keeps between DKG parts 1 and 2. Wiped from memory when
+/// dropped.
+pub struct Round1Secret {
+    identifier: u16,
+    min_signers: u16,
+    max_signers: u16,
+    coefficients: Vec<Scalar>,
+}
+
+/// Broadcast by every participant in DKG part 1.
+#[derive(Debug, Clone)]
+pub struct Round1Package {
+    pub sender: u16,
+    commitments: Vec<EdwardsPoint>,
+    proof_r: EdwardsPoint,
+    proof_mu: Scalar,
+}
+
+/// Secret state a participant keeps between DKG parts 2 and 3. Wiped from memory when
+/// dropped.
+pub struct Round2Secret {
+    identifier: u16,
+    min_signers: u16,
+    max_signers: u16,
+    commitments: Vec<EdwardsPoint>,
+    own_share: Scalar,
+}
+
+/// Sent privately from `sender` to `receiver` in DKG part 2. The share is secret: `Debug`
+/// leaves it out, and it is wiped from memory when dropped.
+#[derive(Clone)]
+pub struct Round2Package {
+    pub sender: u16,
+    pub receiver: u16,
+    signing_share: Scalar,
+}
+
+/// A participant's long-lived signing share produced by the DKG. Wiped from memory when
+/// dropped.
+#[derive(Clone)]
+pub struct KeyShare {
+    identifier: u16,
+    signing_share: Scalar,
+    public_key_package: PublicKeyPackage,
+}
+
+/// Public output of the DKG, identical for every participant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKeyPackage {
+    group_public_key: EdwardsPoint,
+    verifying_shares: BTreeMap<u16, EdwardsPoint>,
+    min_signers: u16,
+}
+
+impl Zeroize for Round1Secret {
+    fn zeroize(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
+impl Drop for Round1Secret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Round1Secret {}
+
+impl Zeroize for Round2Secret {
+    fn zeroize(&mut self) {
+        self.own_share.zeroize();
+    }
+}
+
+impl Drop for Round2Secret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Round2Secret {}
+
+impl Zeroize for Round2Package {
+    fn zeroize(&mut self) {
+        self.signing_share.zeroize();
+    }
+}
+
+impl Drop for Round2Package {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Round2Package {}
+
+impl std::fmt::Debug for Round2Package {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Round2Package")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Zeroize for KeyShare {
+    fn zeroize(&mut self) {
+        self.signing_share.zeroize();
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for KeyShare {}
+
+impl Round2Package {
+    /// Rebuilds a package received over a private channel. A non-canonical share is
+    /// `InvalidKey`.
+    pub fn from_bytes(sender: u16, receiver: u16, signing_share: &[u8; 32]) -> Result<Self> {
+        Ok(Round2Package { sender, receiver, signing_share: canonical_scalar(signing_share)? })
+    }
+
+    /// The canonical encoding of the secret share, wiped from memory when dropped.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.signing_share.to_bytes())
+    }
+}
+
+impl Round1Package {
+    /// Rebuilds a package received over the network from its compressed commitments and
+    /// the proof of knowledge `(R, mu)`. Points that do not decompress, or a non-canonical
+    /// `mu`, are `InvalidKey`; the remaining checks happen in `dkg_part2` and `dkg_part3`.
+    pub fn from_bytes(sender: u16, commitments: &[[u8; 32]], proof_r: &[u8; 32], proof_mu: &[u8; 32]) -> Result<Self> {
+        let proof_mu = Option::from(Scalar::from_canonical_bytes(*proof_mu))
+            .ok_or_else(|| IdentityError::InvalidKey("Proof scalar is not canonical".to_string()))?;
+        Ok(Round1Package {
+            sender,
+            commitments: commitments.iter().map(decompress).collect::<Result<_>>()?,
+            proof_r: decompress(proof_r)?,
+            proof_mu,
+        })
+    }
+
+    pub fn commitment_bytes(&self) -> Vec<[u8; 32]> {
+        self.commitments.iter().map(|commitment| commitment.compress().to_bytes()).collect()
+    }
+
+    /// The proof of knowledge as `(R, mu)`.
+    pub fn proof_bytes(&self) -> ([u8; 32], [u8; 32]) {
+        (self.proof_r.compress().to_bytes(), self.proof_mu.to_bytes())
+    }
+}
+
+fn proof_of_knowledge_challenge(identifier: u16, commitment: &EdwardsPoint, r: &EdwardsPoint) -> Scalar {
+    hdkg(&[
+        identifier_scalar(identifier).as_bytes().as_slice(),
+        commitment.compress().as_bytes(),
+        r.compress().as_bytes(),
+    ]
+    .concat())
+}
+
+/// DKG part 1: samples this participant's secret polynomial and returns the package to
+/// broadcast to every other participant.
+pub fn dkg_part1(identifier: u16, max_signers: u16, min_signers: u16) -> Result<(Round1Secret, Round1Package)> {
+    if min_signers < 2 || min_signers > max_signers {
+        return Err(IdentityError::KeyGenerationError(format!(
+            "Threshold must satisfy 2 <= min_signers ({}) <= max_signers ({})",
+            min_signers, max_signers
+        )));
+    }
+    validate_identifier(identifier, max_signers)?;
+
+    let coefficients: Vec<Scalar> = (0..min_signers).map(|_| random_scalar()).collect();
+    let commitments: Vec<EdwardsPoint> = coefficients.iter().map(|a| ED25519_BASEPOINT_POINT * a).collect();
+
+    // Schnorr proof of knowledge of the constant term, binding it to this identifier.
+    let k = random_scalar();
+    let proof_r = ED25519_BASEPOINT_POINT * k;
+    let c = proof_of_knowledge_challenge(identifier, &commitments[0], &proof_r);
+    let proof_mu = k + coefficients[0] * c;
+
+    Ok((
+        Round1Secret { identifier, min_signers, max_signers, coefficients },
+        Round1Package { sender: identifier, commitments, proof_r, proof_mu },
+    ))
+}
+
+fn check_round1_packages(identifier: u16, min_signers: u16, max_signers: u16, packages: &[Round1Package]) -> Result<()> {
+    let senders: BTreeSet<u16> = packages.iter().map(|package| package.sender).collect();
+    if senders.len() != packages.len() || packages.len() != (max_signers - 1) as usize || senders.contains(&identifier) {
+        return Err(IdentityError::KeyGenerationError(format!(
+            "Expected exactly one round 1 package from each of the other {} participants",
+            max_signers - 1
+        )));
+    }
+    for package in packages {
+        validate_identifier(package.sender, max_signers)?;
+        if package.commitments.len() != min_signers as usize {
+            return Err(IdentityError::KeyGenerationError(format!(
+                "Participant {} committed to {} coefficients, expected {}",
+                package.sender,
+                package.commitments.len(),
+                min_signers
+            )));
+        }
+        // The identity and other small-order points carry no secret: accepting them would
+        // let a participant contribute nothing (or cancel out others) while passing the
+        // proof of knowledge.
+        if package.commitments.iter().chain([&package.proof_r]).any(EdwardsPoint::is_small_order) {
+            return Err(IdentityError::KeyGenerationError(format!(
+                "Participant {} sent an identity or small-order commitment",
+                package.sender
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// DKG part 2: checks every other participant's proof of knowledge and computes the
+/// secret share destined for each of them.
+pub fn dkg_part2(secret: Round1Secret, round1_packages: &[Round1Package]) -> Result<(Round2Secret, Vec<Round2Package>)> {
+    check_round1_packages(secret.identifier, secret.min_signers, secret.max_signers, round1_packages)?;
+
+    for package in round1_packages {
+        let c = proof_of_knowledge_challenge(package.sender, &package.commitments[0], &package.proof_r);
+        if ED25519_BASEPOINT_POINT * package.proof_mu != package.proof_r + package.commitments[0] * c {
+            return Err(IdentityError::KeyGenerationError(format!(
+                "Invalid proof of knowledge from participant {}",
+                package.sender
+            )));
+        }
+    }
+
+    let round2_packages = round1_packages
+        .iter()
+        .map(|package| Round2Package {
+            sender: secret.identifier,
+            receiver: package.sender,
+            signing_share: evaluate_polynomial(&secret.coefficients, identifier_scalar(package.sender)),
+        })
+        .collect();
+
+    let own_share = evaluate_polynomial(&secret.coefficients, identifier_scalar(secret.identifier));
+    let commitments = secret.coefficients.iter().map(|a| ED25519_BASEPOINT_POINT * a).collect();
+    Ok((
+        Round2Secret {
+            identifier: secret.identifier,
+            min_signers: secret.min_signers,
+            max_signers: secret.max_signers,
+            commitments,
+            own_share,
+        },
+        round2_packages,
+    ))
+}
+
+/// DKG part 3: verifies the shares received from every other participant against their
+/// round 1 commitments and combines them into this participant's `KeyShare`.
+pub fn dkg_part3(
+    secret: Round2Secret,
+    round1_packages: &[Round1Package],
+    round2_packages: &[Round2Package],
+) -> Result<KeyShare> {
+    check_round1_packages(secret.identifier, secret.min_signers, secret.max_signers, round1_packages)?;
+
+    let x = identifier_scalar(secret.identifier);
+    let mut signing_share = secret.own_share;
+    for package in round1_packages {
+        let received = round2_packages
+            .iter()
+            .find(|share| share.sender == package.sender && share.receiver == secret.identifier)
+            .ok_or_else(|| {
+                IdentityError::KeyGenerationError(format!("Missing round 2 share from participant {}", package.sender))
+            })?;
+        if ED25519_BASEPOINT_POINT * received.signing_share != evaluate_commitment(&package.commitments, x) {
+            return Err(IdentityError::KeyGenerationError(format!(
+                "Share from participant {} does not match its commitment",
+                package.sender
+            )));
+        }
+        signing_share += received.signing_share;
+    }
+
+    // Every participant can derive the full public package from the broadcast commitments.
+    let all_commitments: Vec<&[EdwardsPoint]> = round1_packages
+        .iter()
+        .map(|package| package.commitments.as_slice())
+        .chain(std::iter::once(secret.commitments.as_slice()))
+        .collect();
+    let group_public_key = all_commitments.iter().map(|commitments| commitments[0]).sum::<EdwardsPoint>();
+    let verifying_shares = (1..=secret.max_signers)
+        .map(|participant| {
+            let share = all_commitments
+                .iter()
+                .map(|commitments| evaluate_commitment(commitments, identifier_scalar(participant)))
+                .sum::<EdwardsPoint>();
+            (participant, share)
+        })
+        .collect();
+
+    Ok(KeyShare {
+        identifier: secret.identifier,
+        signing_share,
+        public_key_package: PublicKeyPackage {
+            group_public_key,
+            verifying_shares,
+            min_signers: secret.min_signers,
+        },
+    })
+}
+
+impl KeyShare {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn public_key_package(&self) -> &PublicKeyPackage {
+        &self.public_key_package
+    }
+}
+
+impl PublicKeyPackage {
+    /// The group public key; aggregated signatures verify against it as plain Ed25519.
+    pub fn group_public_key(&self) -> PublicKey {
+        PublicKey::from_bytes(self.group_public_key.compress().to_bytes().to_vec())
+    }
+
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+}
+
+// --- Signing ---
+/// Single-use nonces for one signing session. Must never be reused. Wiped from memory
+/// when dropped.
+pub struct SigningNonces {
+    hiding: Scalar,
+    binding: Scalar,
+}
+
+impl Zeroize for SigningNonces {
+    fn zeroize(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningNonces {}
+
+/// Public commitments to a participant's `SigningNonces`, sent to the coordinator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningCommitment {
+    pub identifier: u16,
+    hiding: EdwardsPoint,
+    binding: EdwardsPoint,
+}
+
+/// A participant's contribution to the aggregated signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureShare {
+    pub identifier: u16,
+    share: Scalar,
+}
+
+impl SigningCommitment {
+    pub fn hiding_bytes(&self) -> [u8; 32] {
+        self.hiding.compress().to_bytes()
+    }
+
+    pub fn binding_bytes(&self) -> [u8; 32] {
+        self.binding.compress().to_bytes()
+    }
+
+    /// Rebuilds a commitment received from a participant. Points that do not decompress,
+    /// or that have small order (including the identity), are `InvalidKey`.
+    pub fn from_bytes(identifier: u16, hiding: &[u8; 32], binding: &[u8; 32]) -> Result<Self> {
+        let (hiding, binding) = (decompress(hiding)?, decompress(binding)?);
+        if hiding.is_small_order() || binding.is_small_order() {
+            return Err(IdentityError::InvalidKey("Commitment point has small order".to_string()));
+        }
+        Ok(SigningCommitment { identifier, hiding, binding })
+    }
+}
+
+impl SignatureShare {
+    /// Rebuilds a share received from a participant. A non-canonical scalar is `InvalidKey`.
+    pub fn from_bytes(identifier: u16, share: &[u8; 32]) -> Result<Self> {
+        Ok(SignatureShare { identifier, share: canonical_scalar(share)? })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.share.to_bytes()
+    }
+}
+
+fn generate_nonce(secret: &Scalar) -> Scalar {
+    let mut random_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut random_bytes);
+    h3(&[random_bytes.as_slice(), secret.as_bytes()].concat())
+}
+
+/// Signing round 1: generates fresh nonces and the commitment to publish to the coordinator.
+pub fn commit(key_share: &KeyShare) -> (SigningNonces, SigningCommitment) {
+    let hiding = generate_nonce(&key_share.signing_share);
+    let binding = generate_nonce(&key_share.signing_share);
+    (
+        SigningNonces { hiding, binding },
+        SigningCommitment {
+            identifier: key_share.identifier,
+            hiding: ED25519_BASEPOINT_POINT * hiding,
+            binding: ED25519_BASEPOINT_POINT * binding,
+        },
+    )
+}
+
+fn signer_set(commitments: &[SigningCommitment], package: &PublicKeyPackage) -> Result<BTreeSet<u16>> {
+    let signers: BTreeSet<u16> = commitments.iter().map(|commitment| commitment.identifier).collect();
+    if signers.len() != commitments.len() {
+        return Err(IdentityError::SignatureError("Duplicate signer in commitment list".to_string()));
+    }
+    if signers.len() < package.min_signers as usize {
+        return Err(IdentityError::SignatureError(format!(
+            "Insufficient signers: {} provided, {} required",
+            signers.len(),
+            package.min_signers
+        )));
+    }
+    if let Some(unknown) = signers.iter().find(|id| !package.verifying_shares.contains_key(id)) {
+        return Err(IdentityError::SignatureError(format!("Unknown signer {}", unknown)));
+    }
+    Ok(signers)
+}
+
+fn binding_factors(package: &PublicKeyPackage, message: &[u8], commitments: &[SigningCommitment]) -> BTreeMap<u16, Scalar> {
+    let mut sorted = commitments.to_vec();
+    sorted.sort_by_key(|commitment| commitment.identifier);
+
+    let mut encoded_commitments = Vec::with_capacity(sorted.len() * 96);
+    for commitment in &sorted {
+        encoded_commitments.extend_from_slice(identifier_scalar(commitment.identifier).as_bytes());
+        encoded_commitments.extend_from_slice(commitment.hiding.compress().as_bytes());
+        encoded_commitments.extend_from_slice(commitment.binding.compress().as_bytes());
+    }
+
+    let mut prefix = Vec::with_capacity(32 + 64 + 64);
+    prefix.extend_from_slice(package.group_public_key.compress().as_bytes());
+    prefix.extend_from_slice(&h4(message));
+    prefix.extend_from_slice(&h5(&encoded_commitments));
+
+    sorted
+        .iter()
+        .map(|commitment| {
+            let input = [prefix.as_slice(), identifier_scalar(commitment.identifier).as_bytes()].concat();
+            (commitment.identifier, h1(&input))
+        })
+        .collect()
+}
+
+fn group_commitment(commitments: &[SigningCommitment], factors: &BTreeMap<u16, Scalar>) -> EdwardsPoint {
+    commitments
+        .iter()
+        .map(|commitment| commitment.hiding + commitment.binding * factors[&commitment.identifier])
+        .sum()
+}
+
+fn challenge(group_commitment: &EdwardsPoint, group_public_key: &EdwardsPoint, message: &[u8]) -> Scalar {
+    h2(&[
+        group_commitment.compress().as_bytes().as_slice(),
+        group_public_key.compress().as_bytes(),
+        message,
+    ]
+    .concat())
+}
+
+/// Signing round 2: produces this participant's signature share. Consumes the nonces so
+/// they cannot be reused for another message.
+pub fn sign(key_share: &KeyShare, nonces: SigningNonces, message: &[u8], commitments: &[SigningCommitment]) -> Result<SignatureShare> {
+    let package = &key_share.public_key_package;
+    let signers = signer_set(commitments, package)?;
+    if !signers.contains(&key_share.identifier) {
+        return Err(IdentityError::SignatureError(format!(
+            "Signer {} is not part of the commitment list",
+            key_share.identifier
+        )));
+    }
+
+    let factors = binding_factors(package, message, commitments);
+    let r = group_commitment(commitments, &factors);
+    let c = challenge(&r, &package.group_public_key, message);
+    let lambda = lagrange_coefficient(key_share.identifier, &signers);
+
+    let share = nonces.hiding + nonces.binding * factors[&key_share.identifier] + lambda * key_share.signing_share * c;
+    Ok(SignatureShare { identifier: key_share.identifier, share })
+}
+
+/// Verifies every signature share and combines them into a standard 64-byte Ed25519
+/// signature (`R || s`) over `message` for the group public key.
+pub fn aggregate(
+    package: &PublicKeyPackage,
+    message: &[u8],
+    commitments: &[SigningCommitment],
+    shares: &[SignatureShare],
+) -> Result<Vec<u8>> {
+    let signers = signer_set(commitments, package)?;
+    let share_ids: BTreeSet<u16> = shares.iter().map(|share| share.identifier).collect();
+    if share_ids != signers || shares.len() != signers.len() {
+        return Err(IdentityError::SignatureError(format!(
+            "Expected one signature share from each of the {} committed signers, got {}",
+            signers.len(),
+            shares.len()
+        )));
+    }
+
+    let factors = binding_factors(package, message, commitments);
+    let r = group_commitment(commitments, &factors);
+    let c = challenge(&r, &package.group_public_key, message);
+
+    for share in shares {
+        let commitment = commitments
+            .iter()
+            .find(|commitment| commitment.identifier == share.identifier)
+            .expect("share identifiers match commitment identifiers");
+        let lambda = lagrange_coefficient(share.identifier, &signers);
+        let expected = commitment.hiding
+            + commitment.binding * factors[&share.identifier]
+            + package.verifying_shares[&share.identifier] * (c * lambda);
+        if ED25519_BASEPOINT_POINT * share.share != expected {
+            return Err(IdentityError::SignatureError(format!(
+                "Invalid signature share from participant {}",
+                share.identifier
+            )));
+        }
+    }
+
+    let s: Scalar = shares.iter().map(|share| share.share).sum();
+    let mut signature = Vec::with_capacity(64);
+    signature.extend_from_slice(r.compress().as_bytes());
+    signature.extend_from_slice(s.as_bytes());
+    Ok(signature)
+}
@@ -0,0 +1,77 @@
+// Multi-device identities: one primary key plus the keys of other devices acting for it.
+
+use crate::{IdentityError, KeyPair, PublicKey, Result};
+
+/// The public keys of all devices belonging to one identity. The first key is the primary
+/// key, which the identity's DID is derived from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdentitySet {
+    keys: Vec<PublicKey>,
+}
+
+impl IdentitySet {
+    pub fn new(primary: PublicKey) -> Self {
+        IdentitySet { keys: vec![primary] }
+    }
+
+    /// Adds a device key. Returns false if the key is already in the set.
+    pub fn add_device(&mut self, public_key: PublicKey) -> bool {
+        if self.keys.contains(&public_key) {
+            return false;
+        }
+        self.keys.push(public_key);
+        true
+    }
+
+    pub fn primary(&self) -> Option<&PublicKey> {
+        self.keys.first()
+    }
+
+    pub fn keys(&self) -> &[PublicKey] {
+        &self.keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Succeeds if any device key in the set verifies `signature` over `message`.
+    pub fn verify_any(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        if self
+            .keys
+            .iter()
+            .any(|public_key| crate::verify_with_public_key(public_key, message, signature).is_ok())
+        {
+            return Ok(());
+        }
+        Err(IdentityError::SignatureError("No key in the identity set verifies the signature".to_string()))
+    }
+}
+
+/// Builds a set from device keys, using the first as primary. Duplicates are skipped.
+impl FromIterator<PublicKey> for IdentitySet {
+    fn from_iter<I: IntoIterator<Item = PublicKey>>(iter: I) -> Self {
+        let mut set = IdentitySet::default();
+        for public_key in iter {
+            set.add_device(public_key);
+        }
+        set
+    }
+}
+
+/// Builds a set from the public halves of `keypairs`, using the first as primary.
+impl FromIterator<KeyPair> for IdentitySet {
+    fn from_iter<I: IntoIterator<Item = KeyPair>>(iter: I) -> Self {
+        iter.into_iter().map(|keypair| keypair.public_key).collect()
+    }
+}
+
+impl<'a> FromIterator<&'a KeyPair> for IdentitySet {
+    fn from_iter<I: IntoIterator<Item = &'a KeyPair>>(iter: I) -> Self {
+        iter.into_iter().map(|keypair| keypair.public_key.clone()).collect()
+    }
+}
@@ -0,0 +1,109 @@
+// JSON Web Key form of Ed25519 keys (RFC 8037 "OKP" keys), for JOSE interop.
+//
+// Coordinates are base64url without padding, as RFC 7515 requires; padded input is
+// rejected rather than tolerated.
+//
+// A public JWK can carry a proof of possession: a `"pop"` member holding a server nonce
+// and a signature over it by the JWK's own key, made in a dedicated signature domain.
+
+use multibase::Base;
+use serde_json::{json, Map, Value};
+
+use crate::signer::HardwareSigner;
+use crate::{IdentityError, PrivateKey, PublicKey, Result, SignatureDomain};
+
+const KTY_OKP: &str = "OKP";
+const CRV_ED25519: &str = "Ed25519";
+const POP_DOMAIN: SignatureDomain<'static> = SignatureDomain::Custom(b"jwk-proof-of-possession/v1");
+
+fn field<'a>(jwk: &'a Map<String, Value>, name: &str) -> Result<&'a str> {
+    jwk.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| IdentityError::InvalidKey(format!("JWK is missing the string member {:?}", name)))
+}
+
+// Checks `kty` and `crv` and returns the JWK's members.
+fn ed25519_members(jwk: &Value) -> Result<&Map<String, Value>> {
+    let members = jwk.as_object().ok_or_else(|| IdentityError::InvalidKey("JWK must be a JSON object".to_string()))?;
+    let kty = field(members, "kty")?;
+    if kty != KTY_OKP {
+        return Err(IdentityError::InvalidKey(format!("JWK kty must be {:?}, got {:?}", KTY_OKP, kty)));
+    }
+    let crv = field(members, "crv")?;
+    if crv != CRV_ED25519 {
+        return Err(IdentityError::InvalidKey(format!("JWK crv must be {:?}, got {:?}", CRV_ED25519, crv)));
+    }
+    Ok(members)
+}
+
+impl PublicKey {
+    /// `{"kty":"OKP","crv":"Ed25519","x":...}` with `x` in unpadded base64url.
+    pub fn to_jwk(&self) -> Result<Value> {
+        crate::verifying_key_from(self)?;
+        Ok(json!({ "kty": KTY_OKP, "crv": CRV_ED25519, "x": Base::Base64Url.encode(self.as_bytes()) }))
+    }
+
+    pub fn from_jwk(jwk: &Value) -> Result<Self> {
+        let members = ed25519_members(jwk)?;
+        Ok(PublicKey::from_bytes(crate::decode_key_text(field(members, "x")?, Base::Base64Url, "Public key")?))
+    }
+}
+
+impl PublicKey {
+    /// The public JWK plus a `"pop"` member proving that `signer` holds this key:
+    /// `{"nonce": ..., "signature": ...}`, both unpadded base64url, where the signature is
+    /// over `nonce` in the proof-of-possession domain. `signer` must hold this key.
+    pub fn to_jwk_with_pop(&self, signer: &(impl HardwareSigner + ?Sized), nonce: &[u8]) -> Result<Value> {
+        if &signer.public_key()? != self {
+            return Err(IdentityError::InvalidKey("Signer does not hold the JWK's key".to_string()));
+        }
+        let signature = POP_DOMAIN.sign_with(signer, nonce)?;
+        let mut jwk = self.to_jwk()?;
+        jwk["pop"] = json!({ "nonce": Base::Base64Url.encode(nonce), "signature": Base::Base64Url.encode(signature) });
+        Ok(jwk)
+    }
+
+    /// Reads a JWK made by `to_jwk_with_pop` and returns its key once the proof of
+    /// possession checks out: the proof must answer `expected_nonce` and be signed by the
+    /// JWK's own key. A missing proof or wrong nonce is `InvalidKey`.
+    pub fn verify_jwk_pop(jwk: &Value, expected_nonce: &[u8]) -> Result<PublicKey> {
+        let public_key = PublicKey::from_jwk(jwk)?;
+        let pop = jwk
+            .get("pop")
+            .and_then(Value::as_object)
+            .ok_or_else(|| IdentityError::InvalidKey("JWK has no proof of possession".to_string()))?;
+
+        let nonce = crate::decode_text(field(pop, "nonce")?, Base::Base64Url, "proof of possession nonce")?;
+        if nonce != expected_nonce {
+            return Err(IdentityError::InvalidKey("JWK proof of possession answers a different nonce".to_string()));
+        }
+        let signature = crate::decode_text(field(pop, "signature")?, Base::Base64Url, "proof of possession signature")?;
+        crate::verify_with_public_key(&public_key, &POP_DOMAIN.tagged_message(&nonce), &signature)?;
+        Ok(public_key)
+    }
+}
+
+impl PrivateKey {
+    /// The public JWK plus the private key seed as `d`.
+    pub fn to_jwk(&self) -> Result<Value> {
+        let signing_key = crate::signing_key_from(self)?;
+        Ok(json!({
+            "kty": KTY_OKP,
+            "crv": CRV_ED25519,
+            "x": Base::Base64Url.encode(signing_key.verifying_key().as_bytes()),
+            "d": Base::Base64Url.encode(self.as_bytes()),
+        }))
+    }
+
+    /// Reads `d` and checks that `x` is the public key it derives.
+    pub fn from_jwk(jwk: &Value) -> Result<Self> {
+        let members = ed25519_members(jwk)?;
+        let private_key = PrivateKey::from_bytes(crate::decode_key_text(field(members, "d")?, Base::Base64Url, "Private key")?);
+        let public_key = PublicKey::from_jwk(jwk)?;
+        let derived = crate::signing_key_from(&private_key)?.verifying_key();
+        if derived.as_bytes() != public_key.as_bytes() {
+            return Err(IdentityError::InvalidKey("JWK x does not match the public key of d".to_string()));
+        }
+        Ok(private_key)
+    }
+}
@@ -0,0 +1,182 @@
+// Password-encrypted private key files.
+//
+// The password is stretched with Argon2id into a 256-bit key, which encrypts the private
+// key bytes with XChaCha20-Poly1305. The keystore is a JSON object carrying everything
+// needed to decrypt except the password:
+//
+//     {
+//       "version": 1,
+//       "kdf": { "algorithm": "argon2id", "memoryKib": 19456, "iterations": 2,
+//                "parallelism": 1, "salt": "<hex>" },
+//       "cipher": { "algorithm": "xchacha20poly1305", "nonce": "<hex>" },
+//       "ciphertext": "<hex, including the 16-byte tag>"
+//     }
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use multibase::Base;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{decode_text, IdentityError, PrivateKey, Result};
+
+const KEYSTORE_VERSION: u32 = 1;
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+const SALT_LEN: usize = 16;
+const MIN_SALT_LEN: usize = 8;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+// Keystores may only ask for more work than the defaults we write, and no more than this,
+// so a crafted file cannot make decryption take gigabytes of memory or hours of CPU.
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KdfParams {
+    algorithm: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CipherParams {
+    algorithm: String,
+    nonce: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Keystore {
+    version: u32,
+    kdf: KdfParams,
+    cipher: CipherParams,
+    ciphertext: String,
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .map_err(|e| IdentityError::KeyGenerationError(format!("Random number generator failed: {}", e)))?;
+    Ok(bytes)
+}
+
+fn check_kdf_params(kdf: &KdfParams, salt: &[u8]) -> Result<()> {
+    let bounds = [
+        ("memoryKib", kdf.memory_kib, Params::DEFAULT_M_COST, MAX_MEMORY_KIB),
+        ("iterations", kdf.iterations, Params::DEFAULT_T_COST, MAX_ITERATIONS),
+        ("parallelism", kdf.parallelism, Params::DEFAULT_P_COST, MAX_PARALLELISM),
+    ];
+    for (name, value, min, max) in bounds {
+        if !(min..=max).contains(&value) {
+            return Err(IdentityError::DecodingError(format!(
+                "Keystore {} must be between {} and {}, got {}",
+                name, min, max, value
+            )));
+        }
+    }
+    if salt.len() < MIN_SALT_LEN {
+        return Err(IdentityError::DecodingError(format!(
+            "Keystore salt must be at least {} bytes, got {}",
+            MIN_SALT_LEN,
+            salt.len()
+        )));
+    }
+    Ok(())
+}
+
+fn derive_key(password: &str, kdf: &KdfParams, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    check_kdf_params(kdf, salt)?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| IdentityError::DecodingError(format!("Invalid Argon2 parameters: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(|e| IdentityError::KeyGenerationError(format!("Argon2 key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+fn cipher_for(key: &[u8; 32]) -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(Key::from_slice(key))
+}
+
+impl PrivateKey {
+    /// Encrypts this key under `password` into a JSON keystore (see the module comment for
+    /// the format), using Argon2id with its recommended defaults and a fresh random salt
+    /// and nonce.
+    pub fn to_encrypted_keystore(&self, password: &str) -> Result<String> {
+        let salt = random_bytes::<SALT_LEN>()?;
+        let nonce = random_bytes::<NONCE_LEN>()?;
+        let kdf = KdfParams {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            salt: Base::Base16Lower.encode(salt),
+        };
+
+        let key = derive_key(password, &kdf, &salt)?;
+        let ciphertext = cipher_for(&key)
+            .encrypt(XNonce::from_slice(&nonce), self.as_bytes())
+            .map_err(|e| IdentityError::EncodingError(format!("Keystore encryption failed: {}", e)))?;
+
+        let keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            kdf,
+            cipher: CipherParams { algorithm: CIPHER_ALGORITHM.to_string(), nonce: Base::Base16Lower.encode(nonce) },
+            ciphertext: Base::Base16Lower.encode(ciphertext),
+        };
+        serde_json::to_string(&keystore).map_err(|e| IdentityError::EncodingError(e.to_string()))
+    }
+
+    /// Decrypts a keystore made by `to_encrypted_keystore`. A wrong password, or a keystore
+    /// whose contents were altered, is `DecryptionError`; a malformed or unsupported
+    /// keystore is `DecodingError`. That includes Argon2 parameters below the defaults or
+    /// above a fixed cap (1 GiB of memory, 64 iterations, 16 lanes) and salts shorter than
+    /// 8 bytes, which are rejected before any key derivation runs.
+    pub fn from_encrypted_keystore(json: &str, password: &str) -> Result<PrivateKey> {
+        let keystore: Keystore = serde_json::from_str(json).map_err(|e| IdentityError::DecodingError(e.to_string()))?;
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(IdentityError::DecodingError(format!("Unsupported keystore version {}", keystore.version)));
+        }
+        if keystore.kdf.algorithm != KDF_ALGORITHM || keystore.cipher.algorithm != CIPHER_ALGORITHM {
+            return Err(IdentityError::DecodingError(format!(
+                "Unsupported keystore algorithms {} / {}",
+                keystore.kdf.algorithm, keystore.cipher.algorithm
+            )));
+        }
+
+        let salt = decode_text(&keystore.kdf.salt, Base::Base16Lower, "keystore salt")?;
+        let nonce = decode_text(&keystore.cipher.nonce, Base::Base16Lower, "keystore nonce")?;
+        if nonce.len() != NONCE_LEN {
+            return Err(IdentityError::DecodingError(format!(
+                "Keystore nonce must be {} bytes, got {}",
+                NONCE_LEN,
+                nonce.len()
+            )));
+        }
+        let ciphertext = decode_text(&keystore.ciphertext, Base::Base16Lower, "keystore ciphertext")?;
+
+        let key = derive_key(password, &keystore.kdf, &salt)?;
+        let plaintext = cipher_for(&key)
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| IdentityError::DecryptionError("Wrong password or corrupted keystore".to_string()))?;
+        let plaintext = Zeroizing::new(plaintext);
+        if plaintext.len() != KEY_LEN {
+            return Err(IdentityError::DecodingError(format!(
+                "Keystore must hold a {}-byte private key, got {}",
+                KEY_LEN,
+                plaintext.len()
+            )));
+        }
+        Ok(PrivateKey::from_bytes(plaintext.to_vec()))
+    }
+}
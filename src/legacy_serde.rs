@@ -0,0 +1,82 @@
+// The byte-array serde form keys used before they serialized as base58btc strings.
+//
+// For fields that must keep reading and writing `[1, 2, 3, ...]`, e.g. stored data that
+// predates the change:
+//
+//     #[derive(Serialize, Deserialize)]
+//     struct Stored {
+//         #[serde(with = "dverse_identity::legacy_serde::public_key")]
+//         key: PublicKey,
+//     }
+//
+// `PublicKey::migrate_from_legacy` and `PrivateKey::migrate_from_legacy` convert such
+// values one at a time instead.
+
+/// `PublicKey` as a byte array.
+pub mod public_key {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::PublicKey;
+
+    #[cfg(not(feature = "std"))]
+    use crate::no_std_prelude::*;
+
+    pub fn serialize<S: Serializer>(key: &PublicKey, serializer: S) -> Result<S::Ok, S::Error> {
+        key.as_bytes().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PublicKey, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(PublicKey::from_bytes)
+    }
+}
+
+/// `PrivateKey` as a byte array.
+pub mod private_key {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::PrivateKey;
+
+    #[cfg(not(feature = "std"))]
+    use crate::no_std_prelude::*;
+
+    pub fn serialize<S: Serializer>(key: &PrivateKey, serializer: S) -> Result<S::Ok, S::Error> {
+        key.as_bytes().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PrivateKey, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(PrivateKey::from_bytes)
+    }
+}
+
+/// `KeyPair` as `{"private_key": [...], "public_key": [...]}`, the shape its derived
+/// serde impls produced.
+pub mod keypair {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{KeyPair, PrivateKey, PublicKey};
+
+    #[derive(Serialize)]
+    struct LegacyRef<'a> {
+        #[serde(with = "super::private_key")]
+        private_key: &'a PrivateKey,
+        #[serde(with = "super::public_key")]
+        public_key: &'a PublicKey,
+    }
+
+    #[derive(Deserialize)]
+    struct Legacy {
+        #[serde(with = "super::private_key")]
+        private_key: PrivateKey,
+        #[serde(with = "super::public_key")]
+        public_key: PublicKey,
+    }
+
+    pub fn serialize<S: Serializer>(keypair: &KeyPair, serializer: S) -> Result<S::Ok, S::Error> {
+        LegacyRef { private_key: &keypair.private_key, public_key: &keypair.public_key }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyPair, D::Error> {
+        let Legacy { private_key, public_key } = Legacy::deserialize(deserializer)?;
+        Ok(KeyPair { private_key, public_key })
+    }
+}
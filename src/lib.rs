@@ -1,10 +1,116 @@
-use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+use ed25519_dalek::{SigningKey, VerifyingKey, Signature as DalekSignature, Signer, Verifier};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use hkdf::Hkdf;
+#[cfg(feature = "std")]
 use rand_core::OsRng;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Serialize, Deserialize};
 use multibase::{encode, decode, Base};
+use sha2::{Digest, Sha256, Sha512};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroizing;
+
+use secure_mem::SecretBytes;
+
+// Without `std`, the collection and formatting items the std prelude provides come from
+// `alloc`. Modules built in both configurations import them from here.
+#[cfg(not(feature = "std"))]
+pub(crate) mod no_std_prelude {
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+#[cfg(feature = "std")]
+pub mod agent;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod blind;
+#[cfg(feature = "std")]
+pub mod capability;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod compact_store;
+#[cfg(feature = "std")]
+pub mod credential;
+#[cfg(feature = "std")]
+pub mod document;
+#[cfg(feature = "std")]
+pub mod extended;
+#[cfg(feature = "std")]
+pub mod frost;
+#[cfg(feature = "std")]
+pub mod identity_set;
+#[cfg(feature = "std")]
+mod jwk;
+#[cfg(feature = "keystore")]
+mod keystore;
+pub mod legacy_serde;
+#[cfg(feature = "std")]
+pub mod limiter;
+#[cfg(feature = "std")]
+mod mnemonic;
+pub mod multicodec;
+#[cfg(feature = "std")]
+pub mod nonce;
+#[cfg(feature = "std")]
+pub mod policy;
+#[cfg(feature = "std")]
+pub mod registration;
+#[cfg(feature = "std")]
+pub mod resolver;
+mod secure_mem;
+pub mod signer;
+#[cfg(feature = "std")]
+pub mod x25519;
+
+#[cfg(feature = "std")]
+pub use agent::IdentityAgent;
+#[cfg(feature = "std")]
+pub use audit::{AuditLog, AuditRecord};
+#[cfg(feature = "std")]
+pub use clock::{Clock, MockClock, MonotonicClock, SystemClock};
+#[cfg(feature = "std")]
+pub use compact_store::CompactDidStore;
+#[cfg(feature = "std")]
+pub use credential::{Profile, ProfileCredential};
+#[cfg(feature = "std")]
+pub use document::{DidDocument, DidDocumentBuilder, DocumentOrder, DocumentPatch, DocumentProof, KeyUsage, Service, VerificationMethod};
+#[cfg(feature = "std")]
+pub use extended::ExtendedKeyPair;
+#[cfg(feature = "std")]
+pub use identity_set::IdentitySet;
+#[cfg(feature = "std")]
+pub use limiter::VerificationLimiter;
+#[cfg(feature = "std")]
+pub use multicodec::register_multicodec;
+pub use multicodec::KeyType;
+#[cfg(feature = "std")]
+pub use nonce::{InMemoryNonceStore, NonceStore, SignatureDedup};
+#[cfg(feature = "std")]
+pub use policy::{Canonicalization, SigningPolicy};
+#[cfg(feature = "std")]
+pub use registration::{verify_registration, RegistrationStatement};
+#[cfg(feature = "std")]
+pub use resolver::{DidResolver, LocalResolver, ResolutionResult};
+pub use signer::{GuardedSigner, HardwareSigner};
+#[cfg(feature = "std")]
+pub use x25519::{KeyAgreementPublicKey, X25519Public, X25519Secret};
 
 // --- Error Handling ---
+// Non-exhaustive so new variants can be added without breaking downstream `match`es.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum IdentityError {
     KeyGenerationError(String),
     SignatureError(String),
@@ -18,9 +124,36 @@ pub enum IdentityError {
     DalekError(ed25519_dalek::SignatureError),
     MultibaseError(multibase::Error),
     ArrayConversionError(String),
+    ReplayDetected(String),
+    InvalidDocument(String),
+    InvalidCapability(String),
+    AgentLocked(String),
+    UnsupportedSignatureVersion(String),
+    InvalidArgument(String),
+    ResolutionError(String),
+    RateLimited(String),
+    /// A supplied signature is not 64 bytes. Corrupt stored keys are `InvalidKey` instead.
+    InvalidSignatureLength(String),
+    InvalidCredential(String),
+    MessageTooLarge(String),
+    /// Encrypted data could not be decrypted: a wrong password or altered ciphertext.
+    DecryptionError(String),
+    /// An authentication tag or MAC did not match, so the data cannot be trusted.
+    AuthenticationFailed(String),
 }
 
-pub type Result<T> = std::result::Result<T, IdentityError>;
+pub type Result<T> = core::result::Result<T, IdentityError>;
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdentityError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IdentityError::DalekError(err) => Some(err),
+            IdentityError::MultibaseError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 // Implement From traits for easier error conversion
 impl From<ed25519_dalek::SignatureError> for IdentityError {
@@ -36,8 +169,8 @@ impl From<multibase::Error> for IdentityError {
 }
 
 // Implement Display for IdentityError
-impl std::fmt::Display for IdentityError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for IdentityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             IdentityError::KeyGenerationError(msg) => write!(f, "Key Generation Error: {}", msg),
             IdentityError::SignatureError(msg) => write!(f, "Signature Error: {}", msg),
@@ -50,24 +183,96 @@ impl std::fmt::Display for IdentityError {
             IdentityError::DalekError(err) => write!(f, "Cryptographic Error: {}", err),
             IdentityError::MultibaseError(err) => write!(f, "Multibase Error: {}", err),
             IdentityError::ArrayConversionError(msg) => write!(f, "Array Conversion Error: {}", msg),
+            IdentityError::ReplayDetected(msg) => write!(f, "Replay Detected: {}", msg),
+            IdentityError::InvalidDocument(msg) => write!(f, "Invalid DID Document: {}", msg),
+            IdentityError::InvalidCapability(msg) => write!(f, "Invalid Capability: {}", msg),
+            IdentityError::AgentLocked(msg) => write!(f, "Agent Locked: {}", msg),
+            IdentityError::UnsupportedSignatureVersion(msg) => write!(f, "Unsupported Signature Version: {}", msg),
+            IdentityError::InvalidArgument(msg) => write!(f, "Invalid Argument: {}", msg),
+            IdentityError::ResolutionError(msg) => write!(f, "Resolution Error: {}", msg),
+            IdentityError::RateLimited(msg) => write!(f, "Rate Limited: {}", msg),
+            IdentityError::InvalidSignatureLength(msg) => write!(f, "Invalid Signature Length: {}", msg),
+            IdentityError::InvalidCredential(msg) => write!(f, "Invalid Credential: {}", msg),
+            IdentityError::MessageTooLarge(msg) => write!(f, "Message Too Large: {}", msg),
+            IdentityError::DecryptionError(msg) => write!(f, "Decryption Error: {}", msg),
+            IdentityError::AuthenticationFailed(msg) => write!(f, "Authentication Failed: {}", msg),
         }
     }
 }
 
 // --- Key Pair Representation ---
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct PrivateKey(Vec<u8>);
+/// An Ed25519 private key (the 32-byte seed). Serializes as a base58btc multibase string;
+/// see `legacy_serde` for the older byte-array form.
+#[derive(Debug, Clone)]
+pub struct PrivateKey(SecretBytes);
+
+// Deliberately not derived: a derived `PartialEq` compares bytes with an early exit,
+// so its running time reveals how long a matching prefix an attacker-supplied key has.
+// `ct_eq` always looks at every byte. Only the (non-secret) lengths may short-circuit.
+// Do not replace this with `#[derive(PartialEq, Eq)]`.
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+impl Eq for PrivateKey {}
+
+impl ConstantTimeEq for PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
 
 impl PrivateKey {
     pub fn from_bytes(bytes: Vec<u8>) -> Self {
-        PrivateKey(bytes)
+        PrivateKey(SecretBytes::new(bytes))
     }
     pub fn as_bytes(&self) -> &[u8] {
-        &self.0
+        self.0.as_slice()
+    }
+
+    /// The stored 32-byte RFC 8032 seed: the value `KeyPair::from_seed` takes and
+    /// `to_mnemonic` encodes. It is not the signing scalar, which Ed25519 derives by hashing
+    /// the seed with SHA-512 and clamping the lower half. Wiped from memory when dropped.
+    pub fn seed(&self) -> Result<Zeroizing<[u8; 32]>> {
+        let bytes = self.as_bytes();
+        if bytes.len() != 32 {
+            return Err(IdentityError::InvalidKey(format!("Private key must be 32 bytes, got {}", bytes.len())));
+        }
+        let mut seed = Zeroizing::new([0u8; 32]);
+        seed.copy_from_slice(bytes);
+        Ok(seed)
+    }
+
+    /// Whether the key's memory is locked against swapping. Always false unless the
+    /// `secure-mem` feature is enabled and the platform allowed the lock.
+    pub fn is_memory_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+
+    /// Loads a private key stored in the legacy serde shape, a JSON array of byte values,
+    /// as written before keys serialized as base58btc strings.
+    pub fn migrate_from_legacy(value: &serde_json::Value) -> Result<PrivateKey> {
+        Ok(PrivateKey::from_bytes(legacy_key_bytes(value)?))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+impl PrivateKey {
+    /// Signs `message` with this key. The stored bytes are checked to be a 32-byte seed
+    /// first, so a corrupt key surfaces as `IdentityError::InvalidKey` rather than a
+    /// failure deeper in the signing path.
+    pub fn try_sign(&self, message: &[u8]) -> Result<Signature> {
+        let signing_key = signing_key_from(self)?;
+        Ok(Signature(signing_key.sign(message).to_bytes()))
+    }
+}
+
+/// An Ed25519 public key. Unlike `PrivateKey`, equality is the ordinary derived byte
+/// comparison: public keys are not secret, so timing differences reveal nothing.
+/// Serializes as a base58btc multibase string; see `legacy_serde` for the older
+/// byte-array form. Ordered by byte comparison, for use as a sorted-collection key.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PublicKey(Vec<u8>);
 
 impl PublicKey {
@@ -77,6 +282,105 @@ impl PublicKey {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// Loads a public key stored in the legacy serde shape, a JSON array of byte values,
+    /// as written before keys serialized as base58btc strings.
+    pub fn migrate_from_legacy(value: &serde_json::Value) -> Result<PublicKey> {
+        Ok(PublicKey(legacy_key_bytes(value)?))
+    }
+}
+
+// The original `Vec<u8>` newtype encoding: `[1, 2, 3, ...]`.
+fn legacy_key_bytes(value: &serde_json::Value) -> Result<Vec<u8>> {
+    Vec::<u8>::deserialize(value).map_err(|e| IdentityError::DecodingError(format!("Not a legacy key byte array: {}", e)))
+}
+
+// --- Key Serialization ---
+// Both key types serialize as base58btc multibase strings (`"z..."`), which read well in
+// JSON config files. Deserializing checks the base and the 32-byte length.
+const SERIALIZED_KEY_LEN: usize = 32;
+
+fn key_from_multibase<E: serde::de::Error>(text: &str, what: &str) -> core::result::Result<Vec<u8>, E> {
+    let (base, bytes) = decode(text).map_err(|e| E::custom(format!("Invalid {} encoding: {}", what, e)))?;
+    if base != Base::Base58Btc {
+        return Err(E::custom(format!("{} must be base58btc multibase, got {:?}", what, base)));
+    }
+    if bytes.len() != SERIALIZED_KEY_LEN {
+        return Err(E::custom(format!("{} must be {} bytes, got {}", what, SERIALIZED_KEY_LEN, bytes.len())));
+    }
+    Ok(bytes)
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(Base::Base58Btc, &self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        key_from_multibase(&text, "Public key").map(PublicKey)
+    }
+}
+
+impl Serialize for PrivateKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let text = Zeroizing::new(encode(Base::Base58Btc, self.as_bytes()));
+        serializer.serialize_str(&text)
+    }
+}
+
+impl<'de> Deserialize<'de> for PrivateKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let text = Zeroizing::new(String::deserialize(deserializer)?);
+        key_from_multibase(&text, "Private key").map(PrivateKey::from_bytes)
+    }
+}
+
+impl PublicKey {
+    /// Builds a public key from a compressed Edwards Y encoding, checking that it
+    /// decompresses to a curve point and that the encoding is canonical (re-compressing the
+    /// point yields the same bytes). `from_bytes` performs no validation at all.
+    pub fn from_compressed_edwards_y(bytes: &[u8; 32]) -> Result<PublicKey> {
+        let point = CompressedEdwardsY(*bytes)
+            .decompress()
+            .ok_or_else(|| IdentityError::InvalidKey("Bytes do not encode a point on the Ed25519 curve".to_string()))?;
+        if point.compress().as_bytes() != bytes {
+            return Err(IdentityError::InvalidKey("Non-canonical Edwards point encoding".to_string()));
+        }
+        Ok(PublicKey(bytes.to_vec()))
+    }
+
+    /// Verifies `signature` over `message` with this key alone; no `KeyPair` is needed.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        verify_with_public_key(self, message, signature)
+    }
+
+    /// Verifies `signature` and returns the signer's DID, so callers can log who signed
+    /// in the same step.
+    pub fn verify_logged(&self, message: &[u8], signature: &[u8]) -> Result<Did> {
+        verify_with_public_key(self, message, signature)?;
+        Did::from_public_key(self)
+    }
+
+    /// Returns `a` if `choice` is 0 and `b` if it is 1, without branching on `choice`.
+    /// The keys must be the same length; lengths are public, so that check may branch.
+    pub fn conditional_select(a: &PublicKey, b: &PublicKey, choice: Choice) -> Result<PublicKey> {
+        if a.0.len() != b.0.len() {
+            return Err(IdentityError::InvalidKey(format!(
+                "Cannot select between keys of length {} and {}",
+                a.0.len(),
+                b.0.len()
+            )));
+        }
+        Ok(PublicKey(
+            a.0.iter()
+                .zip(&b.0)
+                .map(|(x, y)| u8::conditional_select(x, y, choice))
+                .collect(),
+        ))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -85,110 +389,1433 @@ pub struct KeyPair {
     pub public_key: PublicKey,
 }
 
+// --- Signature Representation ---
+/// A 64-byte Ed25519 signature (`R || s`). Equality and hashing cover all 64 bytes, so
+/// signatures can key sets and maps, e.g. `nonce::SignatureDedup`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature([u8; 64]);
+
+impl Signature {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 64] = bytes
+            .try_into()
+            .map_err(|_| IdentityError::InvalidSignatureLength(format!("Signature must be 64 bytes, got {}", bytes.len())))?;
+        Ok(Signature(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        self.0
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+// Serialized as a byte sequence like the other key types; serde has no derive for [u8; 64].
+impl Serialize for Signature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Signature::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+// --- Multibase ---
+// Codes the multibase table assigns but the linked `multibase` crate cannot decode.
+// Remove entries as the crate gains support for them.
+const UNSUPPORTED_MULTIBASE_CODES: &[(char, &str)] = &[('p', "proquint")];
+
+// `multibase::decode`, except that a prefix from the multibase table which this build
+// cannot decode is reported as `UnsupportedMultibase` naming the base, rather than as an
+// opaque `MultibaseError`. Prefixes outside the table stay `MultibaseError`.
+pub(crate) fn decode_multibase(text: &str) -> Result<(Base, Vec<u8>)> {
+    decode(text).map_err(|err| match err {
+        multibase::Error::UnknownBase(code) => match UNSUPPORTED_MULTIBASE_CODES.iter().find(|(known, _)| *known == code) {
+            Some((_, name)) => IdentityError::UnsupportedMultibase(format!(
+                "Multibase prefix '{}' ({}) is not supported by the linked multibase crate",
+                code, name
+            )),
+            None => IdentityError::MultibaseError(err),
+        },
+        _ => IdentityError::MultibaseError(err),
+    })
+}
+
+// --- Text Encodings ---
+// Hex and padded standard base64, without multibase prefixes. Hex is written lowercase
+// but read in either case.
+fn decode_text(text: &str, base: Base, what: &str) -> Result<Vec<u8>> {
+    let decoded = if base == Base::Base16Lower {
+        // The input may be a private key, so the lowercased copy is wiped too.
+        base.decode(Zeroizing::new(text.to_ascii_lowercase()).as_str())
+    } else {
+        base.decode(text)
+    };
+    decoded.map_err(|e| IdentityError::DecodingError(format!("Invalid {} encoding: {}", what, e)))
+}
+
+fn decode_key_text(text: &str, base: Base, what: &str) -> Result<Vec<u8>> {
+    let bytes = decode_text(text, base, what)?;
+    if bytes.len() != 32 {
+        return Err(IdentityError::InvalidKey(format!("{} must be 32 bytes, got {}", what, bytes.len())));
+    }
+    Ok(bytes)
+}
+
+impl PublicKey {
+    pub fn to_hex(&self) -> String {
+        Base::Base16Lower.encode(&self.0)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        Ok(PublicKey(decode_key_text(hex, Base::Base16Lower, "Public key")?))
+    }
+
+    pub fn to_base64(&self) -> String {
+        Base::Base64Pad.encode(&self.0)
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self> {
+        Ok(PublicKey(decode_key_text(base64, Base::Base64Pad, "Public key")?))
+    }
+
+    /// Base58 (no multibase prefix) of the SHA-256 of the raw key bytes. A stable
+    /// identifier for logs and deduplication that is shorter than the DID.
+    pub fn thumbprint(&self) -> String {
+        Base::Base58Btc.encode(Sha256::digest(&self.0))
+    }
+
+    /// The first 8 bytes of the thumbprint hash as colon-separated lowercase hex, e.g.
+    /// `3b:6a:27:bc:ce:b6:a4:2d`, for display in the style of SSH fingerprints.
+    pub fn fingerprint(&self) -> String {
+        Sha256::digest(&self.0)[..8].iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+    }
+}
+
+impl PrivateKey {
+    pub fn to_hex(&self) -> String {
+        Base::Base16Lower.encode(self.as_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        Ok(PrivateKey::from_bytes(decode_key_text(hex, Base::Base16Lower, "Private key")?))
+    }
+
+    pub fn to_base64(&self) -> String {
+        Base::Base64Pad.encode(self.as_bytes())
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self> {
+        Ok(PrivateKey::from_bytes(decode_key_text(base64, Base::Base64Pad, "Private key")?))
+    }
+}
+
+impl Signature {
+    pub fn to_hex(&self) -> String {
+        Base::Base16Lower.encode(self.0)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        Signature::from_bytes(&decode_text(hex, Base::Base16Lower, "signature")?)
+    }
+
+    pub fn to_base64(&self) -> String {
+        Base::Base64Pad.encode(self.0)
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self> {
+        Signature::from_bytes(&decode_text(base64, Base::Base64Pad, "signature")?)
+    }
+}
+
 // --- DID Representation ---
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+// Serializes as a bare string (serde newtype), which also makes `Did` usable as a JSON
+// object key, e.g. in `HashMap<Did, T>`. Ordered lexicographically by the string, so
+// `BTreeMap<Did, T>` iterates in a deterministic order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Did(String);
 
+// --- Verification Policy ---
+/// How strictly signatures are checked.
+///
+/// - `Strict` (the default) follows RFC 8032 strictly and additionally rejects weak
+///   (small-order) public keys and `R` values. Each message/key has exactly one accepted
+///   signature, which is what consensus and deduplication logic needs.
+/// - `Lenient` is plain cofactorless Ed25519 as done by `KeyPair::verify`. It still requires
+///   a canonical `s`, but accepts weak keys, so a malicious signer can craft signatures that
+///   verify for more than one message.
+/// - `Legacy` additionally accepts `s` values that are not reduced mod the group order, as
+///   produced by some older libraries. Such signatures are malleable: anyone can derive a
+///   second valid signature from the first. Only enable it for a bounded migration window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VerifyPolicy {
+    #[default]
+    Strict,
+    Lenient,
+    Legacy,
+}
+
 // --- KeyPair Implementation ---
 impl KeyPair {
+    const STORAGE_KEY_SALT: &'static [u8] = b"dverse-identity/storage-key/v1";
+
+    /// Generates a key pair from the operating system's random number generator. If the OS
+    /// generator is unavailable (as in some sandboxes) this is `KeyGenerationError`, not a
+    /// panic.
+    #[cfg(feature = "std")]
     pub fn generate() -> Result<Self> {
-        let mut csprng = OsRng;
-        let signing_key = SigningKey::generate(&mut csprng);
-        let verifying_key = signing_key.verifying_key();
+        Self::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair from `rng`. Without `std` there is no OS generator, so this is
+    /// how embedded and WASM targets supply their own entropy source. The seed is drawn
+    /// with the fallible `try_fill_bytes`, so a failing source is `KeyGenerationError`.
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self> {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        rng.try_fill_bytes(seed.as_mut()).map_err(|e| {
+            IdentityError::KeyGenerationError(format!("Random number generator failed: {}", e))
+        })?;
+        Self::from_seed(&seed)
+    }
 
+    /// Generates a key pair together with its minimal DID Document (see
+    /// `Did::to_did_document`), ready to publish.
+    #[cfg(feature = "std")]
+    pub fn generate_with_document() -> Result<(Self, DidDocument)> {
+        let keypair = Self::generate()?;
+        let document = Did::from_public_key(&keypair.public_key)?.to_did_document()?;
+        Ok((keypair, document))
+    }
+
+    /// Deterministically builds a key pair from a 32-byte Ed25519 seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self> {
+        let signing_key = SigningKey::from_bytes(seed);
         Ok(KeyPair {
-            private_key: PrivateKey(signing_key.to_bytes().to_vec()),
-            public_key: PublicKey(verifying_key.to_bytes().to_vec()),
+            private_key: PrivateKey::from_bytes(seed.to_vec()),
+            public_key: PublicKey(signing_key.verifying_key().to_bytes().to_vec()),
+        })
+    }
+
+    /// Rebuilds a key pair from stored private key bytes, recomputing the public key rather
+    /// than trusting a separately stored one.
+    pub fn from_private_key(private_key: &PrivateKey) -> Result<Self> {
+        let signing_key = signing_key_from(private_key)?;
+        Ok(KeyPair {
+            private_key: private_key.clone(),
+            public_key: PublicKey(signing_key.verifying_key().to_bytes().to_vec()),
         })
     }
 
     pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
-        let private_key_bytes: &[u8; 32] = self.private_key.0.as_slice()
-            .try_into()
-            .map_err(|_| IdentityError::ArrayConversionError("Private key bytes are not 32 bytes long".to_string()))?;
-        let signing_key = SigningKey::from_bytes(private_key_bytes);
-        let signature = signing_key.sign(message);
-        Ok(signature.to_bytes().to_vec())
+        Ok(self.private_key.try_sign(message)?.to_vec())
     }
 
     pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
-        let public_key_bytes: &[u8; 32] = self.public_key.0.as_slice()
-            .try_into()
-            .map_err(|_| IdentityError::ArrayConversionError("Public key bytes are not 32 bytes long".to_string()))?;
-        let verifying_key = VerifyingKey::from_bytes(public_key_bytes)?;
+        verify_with_public_key(&self.public_key, message, signature)
+    }
 
-        let signature_bytes: &[u8; 64] = signature
-            .try_into()
-            .map_err(|_| IdentityError::ArrayConversionError("Signature bytes are not 64 bytes long".to_string()))?;
-        let signature = Signature::from_bytes(signature_bytes);
+    pub fn verify_with_policy(&self, message: &[u8], signature: &[u8], policy: VerifyPolicy) -> Result<()> {
+        verify_with_policy(&self.public_key, message, signature, policy)
+    }
+
+    /// Derives a 32-byte symmetric key for encrypting local data, using HKDF-SHA256 over the
+    /// private key with `label` as the info string. Derivation is deterministic: the same
+    /// identity and label always yield the same key, and different labels yield
+    /// independent keys. The key is wiped from memory when dropped.
+    pub fn derive_storage_key(&self, label: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+        let signing_key = signing_key_from(&self.private_key)?;
+        let hkdf = Hkdf::<Sha256>::new(Some(Self::STORAGE_KEY_SALT), signing_key.as_bytes());
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        hkdf.expand(label, key.as_mut())
+            .map_err(|e| IdentityError::KeyGenerationError(format!("HKDF expansion failed: {}", e)))?;
+        Ok(key)
+    }
+
+    pub fn into_parts(self) -> (PrivateKey, PublicKey) {
+        (self.private_key, self.public_key)
+    }
+
+    /// Signs data spread across several buffers without concatenating them. The chunks
+    /// are fed in order into a SHA-512 prehash and signed with Ed25519ph, so the result
+    /// equals `sign_chunks` over any other split of the same bytes, but is NOT
+    /// interchangeable with a plain `sign` signature. Verify with `verify_chunks`.
+    pub fn sign_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(&self, chunks: I) -> Result<Vec<u8>> {
+        self.sign_prehashed(prehash_chunks(chunks), None)
+    }
+
+    pub fn verify_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(&self, chunks: I, signature: &[u8]) -> Result<()> {
+        self.verify_prehashed(prehash_chunks(chunks), None, signature)
+    }
 
-        verifying_key.verify(message, &signature)?;
+    /// Signs a message the caller has fed into `prehashed` incrementally, using Ed25519ph
+    /// (RFC 8032) with an optional context of up to 255 bytes. Ed25519ph signatures never
+    /// verify under plain `verify`, and plain signatures never verify here.
+    pub fn sign_prehashed(&self, prehashed: Sha512, context: Option<&[u8]>) -> Result<Vec<u8>> {
+        let signing_key = signing_key_from(&self.private_key)?;
+        let signature = signing_key.sign_prehashed(prehashed, context)?;
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    /// Verifies an Ed25519ph signature from `sign_prehashed`. The context must match.
+    pub fn verify_prehashed(&self, prehashed: Sha512, context: Option<&[u8]>, signature: &[u8]) -> Result<()> {
+        let verifying_key = verifying_key_from(&self.public_key)?;
+        let signature = signature_from_bytes(signature)?;
+        verifying_key.verify_prehashed(prehashed, context, &signature)?;
         Ok(())
     }
 }
 
+// --- Signing/Verification Helpers ---
+fn signing_key_from(private_key: &PrivateKey) -> Result<SigningKey> {
+    let private_key_bytes: &[u8; 32] = private_key.0.as_slice()
+        .try_into()
+        .map_err(|_| IdentityError::InvalidKey(format!("Private key must be 32 bytes, got {}", private_key.0.len())))?;
+    Ok(SigningKey::from_bytes(private_key_bytes))
+}
+
+fn verifying_key_from(public_key: &PublicKey) -> Result<VerifyingKey> {
+    let public_key_bytes: &[u8; 32] = public_key.0.as_slice()
+        .try_into()
+        .map_err(|_| IdentityError::InvalidKey(format!("Public key must be 32 bytes, got {}", public_key.0.len())))?;
+    Ok(VerifyingKey::from_bytes(public_key_bytes)?)
+}
+
+fn signature_from_bytes(signature: &[u8]) -> Result<DalekSignature> {
+    let signature_bytes: &[u8; 64] = signature
+        .try_into()
+        .map_err(|_| IdentityError::InvalidSignatureLength(format!("Signature must be 64 bytes, got {}", signature.len())))?;
+    Ok(DalekSignature::from_bytes(signature_bytes))
+}
+
+fn verify_with_policy(public_key: &PublicKey, message: &[u8], signature: &[u8], policy: VerifyPolicy) -> Result<()> {
+    let verifying_key = verifying_key_from(public_key)?;
+    match policy {
+        VerifyPolicy::Strict => verifying_key.verify_strict(message, &signature_from_bytes(signature)?)?,
+        VerifyPolicy::Lenient => verifying_key.verify(message, &signature_from_bytes(signature)?)?,
+        VerifyPolicy::Legacy => {
+            // Reducing `s` mod L leaves `[s]B` unchanged, so this accepts exactly the
+            // signatures that verified under implementations that skipped the range check.
+            let mut reduced: [u8; 64] = signature
+                .try_into()
+                .map_err(|_| IdentityError::InvalidSignatureLength(format!("Signature must be 64 bytes, got {}", signature.len())))?;
+            let s_bytes: [u8; 32] = reduced[32..].try_into().expect("slice is 32 bytes");
+            reduced[32..].copy_from_slice(Scalar::from_bytes_mod_order(s_bytes).as_bytes());
+            verifying_key.verify(message, &DalekSignature::from_bytes(&reduced))?
+        }
+    }
+    Ok(())
+}
+
+fn prehash_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(chunks: I) -> Sha512 {
+    let mut hasher = Sha512::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher
+}
+
+fn verify_with_public_key(public_key: &PublicKey, message: &[u8], signature: &[u8]) -> Result<()> {
+    let verifying_key = verifying_key_from(public_key)?;
+    let signature = signature_from_bytes(signature)?;
+    verifying_key.verify(message, &signature)?;
+    Ok(())
+}
+
+/// Verifies `signature` over `message` against a borrowed public key. The key bytes are
+/// never copied and the call does not allocate.
+pub fn verify(public_key: &PublicKey, message: &[u8], signature: &[u8]) -> Result<()> {
+    verify_with_public_key(public_key, message, signature)
+}
+
+/// Verifies hex-encoded inputs, for CLI tools and scripts. Hex may be upper or lower case.
+/// Invalid hex is a
+/// `DecodingError`, a wrong-length key is `InvalidKey`, and a wrong-length signature is
+/// `InvalidSignatureLength`.
+pub fn verify_hex(public_key_hex: &str, message_hex: &str, signature_hex: &str) -> Result<()> {
+    let public_key = PublicKey::from_hex(public_key_hex)?;
+    let message = decode_text(message_hex, Base::Base16Lower, "message")?;
+    let signature = Signature::from_hex(signature_hex)?;
+    verify_with_public_key(&public_key, &message, signature.as_ref())
+}
+
+/// Verifies a `signature (64 bytes) || public_key (32 bytes)` blob over `message` and
+/// returns the embedded public key on success.
+pub fn verify_self_contained(message: &[u8], blob: &[u8]) -> Result<PublicKey> {
+    const SELF_CONTAINED_LEN: usize = 64 + 32;
+    if blob.len() != SELF_CONTAINED_LEN {
+        return Err(IdentityError::ArrayConversionError(format!(
+            "Self-contained blob must be {} bytes (64-byte signature + 32-byte public key), got {}",
+            SELF_CONTAINED_LEN,
+            blob.len()
+        )));
+    }
+
+    let (signature, public_key_bytes) = blob.split_at(64);
+    let public_key = PublicKey(public_key_bytes.to_vec());
+    verify_with_public_key(&public_key, message, signature)?;
+    Ok(public_key)
+}
+
+/// Outcome of `verify_batch_report`: the indices of the items that failed verification.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    pub total: usize,
+    pub failures: Vec<usize>,
+}
+
+impl BatchReport {
+    pub fn all_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn failure_count(&self) -> usize {
+        self.failures.len()
+    }
+
+    pub fn valid_count(&self) -> usize {
+        self.total - self.failures.len()
+    }
+
+    pub fn is_failure(&self, index: usize) -> bool {
+        self.failures.binary_search(&index).is_ok()
+    }
+}
+
+/// Verifies every item without stopping at the first failure and reports which indices
+/// failed. Malformed keys or signatures count as failures.
+pub fn verify_batch_report(items: &[(PublicKey, &[u8], &[u8])]) -> BatchReport {
+    let failures = items
+        .iter()
+        .enumerate()
+        .filter(|(_, (public_key, message, signature))| verify_with_public_key(public_key, message, signature).is_err())
+        .map(|(index, _)| index)
+        .collect();
+    BatchReport { total: items.len(), failures }
+}
+
+/// Verifies all `(public_key, message, signature)` items at once with Ed25519 batch
+/// verification, which is substantially faster than verifying one by one. Fails if any
+/// item is malformed or any signature is invalid, without saying which; use
+/// `verify_batch_indexed` to find the failing entries.
+pub fn verify_batch(items: &[(PublicKey, Vec<u8>, Vec<u8>)]) -> Result<()> {
+    let mut messages = Vec::with_capacity(items.len());
+    let mut signatures = Vec::with_capacity(items.len());
+    let mut verifying_keys = Vec::with_capacity(items.len());
+    for (public_key, message, signature) in items {
+        messages.push(message.as_slice());
+        signatures.push(signature_from_bytes(signature)?);
+        verifying_keys.push(verifying_key_from(public_key)?);
+    }
+
+    ed25519_dalek::verify_batch(&messages, &signatures, &verifying_keys).map_err(|_| {
+        IdentityError::SignatureError(format!("At least one of {} signatures in the batch failed", items.len()))
+    })
+}
+
+/// Like `verify_batch`, but returns the indices of the failing items (empty if all are
+/// valid). Uses the fast batch check first and only falls back to verifying each item
+/// individually when the batch fails.
+pub fn verify_batch_indexed(items: &[(PublicKey, Vec<u8>, Vec<u8>)]) -> Vec<usize> {
+    if verify_batch(items).is_ok() {
+        return Vec::new();
+    }
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, (public_key, message, signature))| verify_with_public_key(public_key, message, signature).is_err())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Verifies each `(public_key, message, signature)` item independently across the rayon
+/// thread pool. Results are returned in input order, one per item.
+#[cfg(feature = "rayon")]
+pub fn verify_parallel(items: &[(PublicKey, Vec<u8>, Vec<u8>)]) -> Vec<Result<()>> {
+    use rayon::prelude::*;
+
+    items
+        .par_iter()
+        .map(|(public_key, message, signature)| verify_with_public_key(public_key, message, signature))
+        .collect()
+}
+
+// --- Checked Serialization ---
+/// Size of the SHA-256 checksum appended by `KeyPair::to_bytes_checked`.
+const CHECKSUM_LEN: usize = 32;
+
+impl KeyPair {
+    /// Encodes the key pair as `private key (32 bytes) || public key (32 bytes) ||
+    /// SHA-256 of the preceding 64 bytes`. The checksum detects truncation and bit-rot; it is
+    /// not a MAC and gives no protection against deliberate tampering or disclosure, so only
+    /// use it for unencrypted development storage.
+    pub fn to_bytes_checked(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.private_key.as_bytes().len() + self.public_key.0.len() + CHECKSUM_LEN);
+        bytes.extend_from_slice(self.private_key.as_bytes());
+        bytes.extend_from_slice(&self.public_key.0);
+        let checksum = Sha256::digest(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    /// Decodes `to_bytes_checked` output, returning `DecodingError` if the length or
+    /// checksum is wrong or the stored public key does not belong to the private key.
+    pub fn from_bytes_checked(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 64 + CHECKSUM_LEN {
+            return Err(IdentityError::DecodingError(format!(
+                "Checked key pair must be {} bytes, got {}",
+                64 + CHECKSUM_LEN,
+                bytes.len()
+            )));
+        }
+        let (payload, checksum) = bytes.split_at(64);
+        if Sha256::digest(payload).as_slice() != checksum {
+            return Err(IdentityError::DecodingError("Key pair checksum does not match; the data is corrupt".to_string()));
+        }
+
+        let keypair = KeyPair::from_private_key(&PrivateKey::from_bytes(payload[..32].to_vec()))?;
+        if keypair.public_key.0 != payload[32..] {
+            return Err(IdentityError::DecodingError("Stored public key does not match the private key".to_string()));
+        }
+        Ok(keypair)
+    }
+}
+
+// --- Versioned Signatures ---
+/// An Ed25519 signature tagged with a scheme version, encoded as
+/// `version (1 byte) || length (1 byte) || signature`. Version 1 is plain Ed25519 with a
+/// 64-byte signature. Decoding rejects unknown versions, so a future scheme can be
+/// introduced without old verifiers misinterpreting its signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionedSignature {
+    version: u8,
+    signature: Vec<u8>,
+}
+
+impl VersionedSignature {
+    pub const CURRENT_VERSION: u8 = 1;
+    const ED25519_SIGNATURE_LEN: usize = 64;
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + self.signature.len());
+        bytes.push(self.version);
+        bytes.push(self.signature.len() as u8);
+        bytes.extend_from_slice(&self.signature);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (&version, rest) = bytes
+            .split_first()
+            .ok_or_else(|| IdentityError::DecodingError("Versioned signature is empty".to_string()))?;
+        if version != Self::CURRENT_VERSION {
+            return Err(IdentityError::UnsupportedSignatureVersion(format!(
+                "Signature version {} is not supported (expected {})",
+                version,
+                Self::CURRENT_VERSION
+            )));
+        }
+
+        let (&length, signature) = rest
+            .split_first()
+            .ok_or_else(|| IdentityError::DecodingError("Versioned signature is missing its length byte".to_string()))?;
+        if length as usize != Self::ED25519_SIGNATURE_LEN || signature.len() != Self::ED25519_SIGNATURE_LEN {
+            return Err(IdentityError::DecodingError(format!(
+                "Version {} signatures must be {} bytes, header says {} and payload has {}",
+                version,
+                Self::ED25519_SIGNATURE_LEN,
+                length,
+                signature.len()
+            )));
+        }
+
+        Ok(VersionedSignature { version, signature: signature.to_vec() })
+    }
+}
+
+impl KeyPair {
+    /// Signs `message` and returns the encoded `VersionedSignature` at the current version.
+    pub fn sign_versioned(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let signature = VersionedSignature {
+            version: VersionedSignature::CURRENT_VERSION,
+            signature: self.sign(message)?,
+        };
+        Ok(signature.to_bytes())
+    }
+
+    pub fn verify_versioned(&self, message: &[u8], versioned_signature: &[u8]) -> Result<()> {
+        let signature = VersionedSignature::from_bytes(versioned_signature)?;
+        self.verify(message, signature.signature())
+    }
+}
+
+// --- Hash Commitments ---
+const COMMITMENT_DOMAIN: &[u8] = b"dverse-identity/commitment/v1";
+
+fn commitment_for(preimage: &[u8]) -> Vec<u8> {
+    Sha256::new().chain_update(COMMITMENT_DOMAIN).chain_update(preimage).finalize().to_vec()
+}
+
+impl KeyPair {
+    /// Commits to `preimage` and signs the commitment, returning `(commitment, signature)`.
+    /// The commitment is a domain-separated SHA-256 hash, so it only hides the preimage if
+    /// the preimage is hard to guess; include a random salt in low-entropy values.
+    pub fn sign_commitment(&self, preimage: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let commitment = commitment_for(preimage);
+        let signature = self.sign(&commitment)?;
+        Ok((commitment, signature))
+    }
+}
+
+/// Verifies that `signature` was made by `public_key` over `commitment`.
+pub fn verify_commitment(public_key: &PublicKey, commitment: &[u8], signature: &[u8]) -> Result<()> {
+    verify_with_public_key(public_key, commitment, signature)
+}
+
+/// Returns true if `preimage` is the value committed to by `commitment`.
+pub fn open_commitment(commitment: &[u8], preimage: &[u8]) -> bool {
+    bool::from(commitment_for(preimage).as_slice().ct_eq(commitment))
+}
+
+// --- Salted Signatures ---
+const SALT_LEN: usize = 16;
+
+fn salted_message(salt: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut salted = Vec::with_capacity(salt.len() + message.len());
+    salted.extend_from_slice(salt);
+    salted.extend_from_slice(message);
+    salted
+}
+
+impl KeyPair {
+    /// Signs `salt || message` with a fresh random 16-byte salt, returning
+    /// `(salt, signature)`. Unlike `sign`, repeating the same message yields a different
+    /// signature each time, so observers cannot spot repeated messages. A failing OS
+    /// random number generator is `SignatureError`.
+    #[cfg(feature = "std")]
+    pub fn sign_salted(&self, message: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng
+            .try_fill_bytes(&mut salt)
+            .map_err(|e| IdentityError::SignatureError(format!("Random number generator failed: {}", e)))?;
+        let signature = self.sign(&salted_message(&salt, message))?;
+        Ok((salt, signature))
+    }
+}
+
+/// Verifies a signature produced by `KeyPair::sign_salted`.
+pub fn verify_salted(public_key: &PublicKey, message: &[u8], salt: &[u8], signature: &[u8]) -> Result<()> {
+    // A fixed salt length keeps `salt || message` unambiguous.
+    if salt.len() != SALT_LEN {
+        return Err(IdentityError::ArrayConversionError(format!("Salt must be {} bytes, got {}", SALT_LEN, salt.len())));
+    }
+    verify_with_public_key(public_key, &salted_message(salt, message), signature)
+}
+
+// --- Signature Domains ---
+/// The application context a signature is made for. Signing in one domain and verifying
+/// in another always fails, so e.g. a login challenge signature cannot be replayed as a
+/// transaction authorization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureDomain<'a> {
+    Login,
+    Transaction,
+    Message,
+    /// An application-defined domain. Custom tags are namespaced apart from the built-in
+    /// domains, so no custom tag can collide with them.
+    Custom(&'a [u8]),
+}
+
+impl SignatureDomain<'_> {
+    // `len (8 bytes, big-endian) || tag || message`; the length prefix keeps tag and
+    // message boundaries unambiguous.
+    fn tagged_message(&self, message: &[u8]) -> Vec<u8> {
+        let (namespace, tag): (&[u8], &[u8]) = match self {
+            SignatureDomain::Login => (b"dverse-identity/domain/", b"login"),
+            SignatureDomain::Transaction => (b"dverse-identity/domain/", b"transaction"),
+            SignatureDomain::Message => (b"dverse-identity/domain/", b"message"),
+            SignatureDomain::Custom(tag) => (b"dverse-identity/domain/custom/", tag),
+        };
+        let tag_len = (namespace.len() + tag.len()) as u64;
+
+        let mut tagged = Vec::with_capacity(8 + tag_len as usize + message.len());
+        tagged.extend_from_slice(&tag_len.to_be_bytes());
+        tagged.extend_from_slice(namespace);
+        tagged.extend_from_slice(tag);
+        tagged.extend_from_slice(message);
+        tagged
+    }
+}
+
+impl SignatureDomain<'_> {
+    /// Signs `message` in this domain with any signer, e.g. to answer a login challenge
+    /// with a hardware key. `KeyPair::sign_in_domain` is the same for software keys.
+    pub fn sign_with(&self, signer: &(impl HardwareSigner + ?Sized), message: &[u8]) -> Result<Vec<u8>> {
+        signer.sign(&self.tagged_message(message))
+    }
+}
+
+impl KeyPair {
+    pub fn sign_in_domain(&self, domain: SignatureDomain<'_>, message: &[u8]) -> Result<Vec<u8>> {
+        domain.sign_with(self, message)
+    }
+
+    pub fn verify_in_domain(&self, domain: SignatureDomain<'_>, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.verify(&domain.tagged_message(message), signature)
+    }
+
+    /// Signs `message` bound to an application-chosen `context`, so one key can be shared
+    /// across protocols without a signature from one being accepted by another. The
+    /// signed bytes are exactly
+    ///
+    /// ```text
+    /// len || "dverse-identity/domain/custom/" || context || message
+    /// ```
+    ///
+    /// where `len` is the 8-byte big-endian length of the namespace plus `context`. This
+    /// is the `SignatureDomain::Custom(context)` framing, so the result equals
+    /// `sign_in_domain(SignatureDomain::Custom(context), message)`.
+    pub fn sign_with_context(&self, message: &[u8], context: &[u8]) -> Result<Vec<u8>> {
+        self.sign_in_domain(SignatureDomain::Custom(context), message)
+    }
+
+    /// Verifies a signature made by `sign_with_context` under the same `context`.
+    pub fn verify_with_context(&self, message: &[u8], context: &[u8], signature: &[u8]) -> Result<()> {
+        self.verify_in_domain(SignatureDomain::Custom(context), message, signature)
+    }
+}
+
+// --- Field Signatures ---
+const FIELDS_DOMAIN: SignatureDomain<'static> = SignatureDomain::Custom(b"length-prefixed-fields/v1");
+
+// `len (8 bytes, big-endian) || field` for each field in order. Every boundary is explicit,
+// so `["ab", "c"]` and `["a", "bc"]` encode differently.
+fn encode_fields(fields: &[&[u8]]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(fields.iter().map(|field| 8 + field.len()).sum());
+    for field in fields {
+        encoded.extend_from_slice(&(field.len() as u64).to_be_bytes());
+        encoded.extend_from_slice(field);
+    }
+    encoded
+}
+
+impl KeyPair {
+    /// Signs a sequence of fields such that the grouping is part of what is signed: each
+    /// field is prefixed with its length as 8 big-endian bytes, and the result is signed in
+    /// its own domain. Naively concatenating fields would let `("ab", "c")` verify as `("a", "bc")`.
+    pub fn sign_fields(&self, fields: &[&[u8]]) -> Result<Vec<u8>> {
+        self.sign_in_domain(FIELDS_DOMAIN, &encode_fields(fields))
+    }
+
+    /// Verifies a signature made by `sign_fields` over exactly these fields.
+    pub fn verify_fields(&self, fields: &[&[u8]], signature: &[u8]) -> Result<()> {
+        self.verify_in_domain(FIELDS_DOMAIN, &encode_fields(fields), signature)
+    }
+}
+
+// --- Contact Bundles ---
+const CONTACT_BUNDLE_DOMAIN: SignatureDomain<'static> = SignatureDomain::Custom(b"contact-bundle/v1");
+
+#[derive(Serialize, Deserialize)]
+struct ContactBundle {
+    did: Did,
+    name: String,
+    signature: Vec<u8>,
+}
+
+impl ContactBundle {
+    fn signing_bytes(did: &Did, name: &str) -> Result<Vec<u8>> {
+        serde_json::to_vec(&(did, name)).map_err(|e| IdentityError::EncodingError(e.to_string()))
+    }
+}
+
+impl KeyPair {
+    /// Exports this identity's DID and `display_name` as a URL-safe, self-signed string
+    /// (base64url multibase) suitable for QR codes and links. Read it with `import_bundle`.
+    pub fn export_bundle(&self, display_name: &str) -> Result<String> {
+        let did = Did::from_public_key(&self.public_key)?;
+        let signature = self.sign_in_domain(CONTACT_BUNDLE_DOMAIN, &ContactBundle::signing_bytes(&did, display_name)?)?;
+        let bundle = ContactBundle { did, name: display_name.to_string(), signature };
+        let json = serde_json::to_vec(&bundle).map_err(|e| IdentityError::EncodingError(e.to_string()))?;
+        Ok(encode(Base::Base64Url, json))
+    }
+}
+
+/// Decodes a bundle from `KeyPair::export_bundle`, checking the self-signature against the
+/// key embedded in the DID before returning `(did, display_name)`.
+pub fn import_bundle(bundle: &str) -> Result<(Did, String)> {
+    let (base, json) = decode_multibase(bundle)?;
+    if base != Base::Base64Url {
+        return Err(IdentityError::UnsupportedMultibase(format!("Unsupported multibase: {:?}", base)));
+    }
+    let bundle: ContactBundle = serde_json::from_slice(&json).map_err(|e| IdentityError::DecodingError(e.to_string()))?;
+
+    let tagged = CONTACT_BUNDLE_DOMAIN.tagged_message(&ContactBundle::signing_bytes(&bundle.did, &bundle.name)?);
+    verify_with_public_key(&bundle.did.to_public_key()?, &tagged, &bundle.signature)?;
+    Ok((bundle.did, bundle.name))
+}
+
+// --- Signed Message Envelope ---
+/// A message bundled with its signature and the DID of the signer, so it can be verified
+/// without any out-of-band key lookup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub signer: Did,
+    pub message: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl SignedMessage {
+    pub fn verify(&self) -> Result<()> {
+        verify_with_public_key(&self.signer.to_public_key()?, &self.message, &self.signature)
+    }
+}
+
+impl KeyPair {
+    pub fn sign_message(&self, message: &[u8]) -> Result<SignedMessage> {
+        Ok(SignedMessage {
+            signer: Did::from_public_key(&self.public_key)?,
+            message: message.to_vec(),
+            signature: self.sign(message)?,
+        })
+    }
+}
+
+/// Lazily verifies each record as it is pulled from `iter`, yielding the record on success
+/// or the verification error. Only one record is held in memory at a time.
+pub fn verify_stream<I: Iterator<Item = SignedMessage>>(iter: I) -> impl Iterator<Item = Result<SignedMessage>> {
+    iter.map(|record| record.verify().map(|_| record))
+}
+
+// --- DID Configuration ---
+/// The method name and multibase encoding used to build DIDs. The default is
+/// `did:dverse:` with Base58Btc, which is what `Did::from_public_key` uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidConfig {
+    pub method: String,
+    pub base: Base,
+}
+
+impl Default for DidConfig {
+    fn default() -> Self {
+        DidConfig { method: "dverse".to_string(), base: Base::Base58Btc }
+    }
+}
+
+// --- DID URL Components ---
+/// The parts of a DID URL (`did:method:id[/path][?query][#fragment]`). `path` keeps its
+/// leading `/`; `query` and `fragment` exclude their `?`/`#` delimiters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidComponents {
+    pub method: String,
+    pub method_specific_id: String,
+    pub path: Option<String>,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
 // --- DID Implementation ---
+struct DecodedKey {
+    key_type: KeyType,
+    public_key: PublicKey,
+    key_agreement_key: Option<[u8; 32]>,
+}
+
 impl Did {
-    // Multicodec for Ed25519 public keys (0xed01)
-    const MULTICODEC_ED25519_PUB: &'static [u8] = &[0xed, 0x01];
-    const DID_DVERSE_PREFIX: &'static str = "did:dverse:";
+    const MULTICODEC_ED25519_PUB: &'static [u8] = multicodec::ED25519_PUB;
+    const MULTICODEC_X25519_PUB: &'static [u8] = multicodec::X25519_PUB;
+    pub(crate) const DID_DVERSE_PREFIX: &'static str = "did:dverse:";
+    #[cfg(feature = "std")]
+    pub(crate) const DID_KEY_PREFIX: &'static str = "did:key:";
+    const AVATAR_SEED_DOMAIN: &'static [u8] = b"dverse-identity/avatar-seed/v1";
+    const SHARD_DOMAIN: &'static [u8] = b"dverse-identity/shard/v1";
+    const THEME_COLOR_DOMAIN: &'static [u8] = b"dverse-identity/theme-color/v1";
+    // Multibases whose alphabet (and prefix) only uses DID `idchar`s, i.e. no padding,
+    // `+`, `/`, spaces or non-ASCII characters.
+    const DID_SAFE_BASES: &'static [Base] = &[
+        Base::Base58Btc,
+        Base::Base58Flickr,
+        Base::Base64Url,
+        Base::Base36Lower,
+        Base::Base36Upper,
+        Base::Base32Lower,
+        Base::Base32Upper,
+        Base::Base32HexLower,
+        Base::Base32HexUpper,
+        Base::Base32Z,
+        Base::Base16Lower,
+        Base::Base16Upper,
+        Base::Base10,
+        Base::Base8,
+        Base::Base2,
+    ];
+
+    /// Checked constructor: accepts `did` only if it is a `did:dverse` DID whose Base58Btc
+    /// body decodes to a valid Ed25519 public key, so bad input fails here rather than at a
+    /// later `to_public_key`. Bad syntax or another method is `InvalidDidFormat`, a body that
+    /// is not Base58Btc (e.g. `did:dverse:f...` or `did:dverse:u...`)
+    /// `UnsupportedMultibase`, a non-Ed25519 key `UnsupportedMulticodec`, a key that is not
+    /// 32 bytes `InvalidKey` and one that is not a curve point `DalekError`. Only the
+    /// canonical spelling is accepted, so equal keys always give equal `Did`s; other
+    /// spellings still decode with `to_public_key`, but only as unchecked `Did`s. Surrounding
+    /// whitespace is trimmed. The `From<&str>` and `From<String>` impls perform none of
+    /// these checks.
+    pub fn new(did: impl Into<String>) -> Result<Did> {
+        let did = Did(did.into().trim().to_string());
+        let encoded_part = did.encoded_key_part()?;
+        let (base, _) = decode_multibase(encoded_part)?;
+        if base != Base::Base58Btc {
+            return Err(IdentityError::UnsupportedMultibase(format!(
+                "DID key is encoded as {:?}, expected Base58Btc: {}",
+                base, did
+            )));
+        }
+        let decoded = Self::decode_key_material(encoded_part)?;
+        if decoded.key_type != KeyType::Ed25519 {
+            return Err(IdentityError::UnsupportedMulticodec(format!(
+                "DID key is {:?}, expected Ed25519: {}",
+                decoded.key_type, did
+            )));
+        }
+        verifying_key_from(&decoded.public_key)?;
+        Ok(did)
+    }
 
     pub fn from_public_key(public_key: &PublicKey) -> Result<Self> {
+        Self::from_public_key_with_config(public_key, &DidConfig::default())
+    }
+
+    /// Builds a DID using `config`'s method name and multibase encoding. The base must only
+    /// produce DID-safe characters (e.g. Base58Btc or Base64Url, not padded Base64).
+    pub fn from_public_key_with_config(public_key: &PublicKey, config: &DidConfig) -> Result<Self> {
         let mut prefixed_key_bytes = Vec::new();
         prefixed_key_bytes.extend_from_slice(Self::MULTICODEC_ED25519_PUB);
         prefixed_key_bytes.extend_from_slice(&public_key.0);
 
         // multibase::encode returns a String, not a Result, so no `?` operator here.
-        let encoded_key = encode(Base::Base58Btc, &prefixed_key_bytes);
+        let encoded_key = encode(config.base, &prefixed_key_bytes);
 
-        let did_string = format!("{}{}", Self::DID_DVERSE_PREFIX, encoded_key);
+        let did_string = format!("did:{}:{}", config.method, encoded_key);
+        Self::validate_syntax(&did_string)?;
 
         Ok(Did(did_string))
     }
 
-    pub fn to_public_key(&self) -> Result<PublicKey> {
-        if !self.0.starts_with(Self::DID_DVERSE_PREFIX) {
-            return Err(IdentityError::InvalidDidFormat(format!("DID does not start with expected prefix: {}", self.0)));
+    /// Decodes the key of a DID built with `config`'s method name. Any multibase is
+    /// accepted, as with `to_public_key`.
+    pub fn to_public_key_with_config(&self, config: &DidConfig) -> Result<PublicKey> {
+        let did = self.trimmed();
+        Self::validate_syntax(did)?;
+        let encoded_part = did
+            .strip_prefix("did:")
+            .and_then(|rest| rest.strip_prefix(config.method.as_str()))
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| IdentityError::InvalidDidFormat(format!("DID is not a did:{} DID: {}", config.method, did)))?;
+        Self::decode_public_key(encoded_part)
+    }
+
+    /// The same key as a `did:key` DID, which generic resolvers understand. Only the
+    /// Ed25519 key is carried over; a dual-key DID's X25519 key is dropped.
+    pub fn to_did_key(&self) -> Result<Did> {
+        Self::from_public_key_with_config(&self.to_public_key()?, &Self::did_key_config())
+    }
+
+    /// Decodes the public key of a `did:key` DID. Any other method is `InvalidDidFormat`.
+    pub fn from_did_key(did: &Did) -> Result<PublicKey> {
+        did.to_public_key_with_config(&Self::did_key_config())
+    }
+
+    fn did_key_config() -> DidConfig {
+        DidConfig { method: "key".to_string(), base: Base::Base58Btc }
+    }
+
+    /// Checks `did` against the W3C DID Core ABNF:
+    ///
+    /// ```text
+    /// did                = "did:" method-name ":" method-specific-id
+    /// method-name        = 1*( %x61-7A / DIGIT )
+    /// method-specific-id = *( *idchar ":" ) 1*idchar
+    /// idchar             = ALPHA / DIGIT / "." / "-" / "_" / pct-encoded
+    /// pct-encoded        = "%" HEXDIG HEXDIG
+    /// ```
+    ///
+    /// The infallible `From` conversions do not validate; every checked constructor does.
+    ///
+    /// This check itself never trims: any whitespace is an error. The methods that parse a
+    /// stored DID (`to_public_key`, `to_public_key_with_config`, `key_type`,
+    /// `key_agreement_key`, `parse`, `resolve_local` and `same_key_any_network`) first strip
+    /// leading and trailing whitespace as `str::trim` does (Unicode `White_Space`, which
+    /// covers spaces, tabs, `\r` and `\n`), so a DID pasted as `" did:dverse:z...\n"` is
+    /// accepted. Whitespace inside the DID and other control characters such as `\0` are
+    /// still rejected.
+    pub fn validate_syntax(did: &str) -> Result<()> {
+        // Checked first so null bytes, control characters and whitespace (including a
+        // trailing newline) get an explicit message.
+        if let Some(invalid) = did.chars().find(|c| c.is_control() || c.is_whitespace()) {
+            return Err(IdentityError::InvalidDidFormat(format!(
+                "DID contains a null, control or whitespace character {:?}: {:?}",
+                invalid, did
+            )));
         }
+        let rest = did.strip_prefix("did:").ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID must start with \"did:\": {}", did))
+        })?;
+        let (method, method_specific_id) = rest.split_once(':').ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID is missing a method-specific identifier: {}", did))
+        })?;
 
-        let encoded_part = &self.0[Self::DID_DVERSE_PREFIX.len()..];
+        if method.is_empty() || !method.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()) {
+            return Err(IdentityError::InvalidDidFormat(format!(
+                "DID method must be one or more lowercase letters or digits: {:?}",
+                method
+            )));
+        }
 
-        let (base, decoded_bytes) = decode(encoded_part)?;
+        if method_specific_id.is_empty() || method_specific_id.ends_with(':') {
+            return Err(IdentityError::InvalidDidFormat(format!(
+                "DID method-specific identifier must not be empty or end with ':': {}",
+                did
+            )));
+        }
 
-        if base != Base::Base58Btc {
-            return Err(IdentityError::UnsupportedMultibase(format!("Unsupported multibase: {:?}", base)));
+        let bytes = method_specific_id.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b':' => i += 1,
+                b'%' if bytes.len() > i + 2 && bytes[i + 1].is_ascii_hexdigit() && bytes[i + 2].is_ascii_hexdigit() => i += 3,
+                _ => {
+                    let invalid = method_specific_id[i..].chars().next().unwrap_or_default();
+                    return Err(IdentityError::InvalidDidFormat(format!(
+                        "Illegal character {:?} in DID method-specific identifier: {}",
+                        invalid, did
+                    )));
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Splits this DID, or DID URL such as a verification method reference
+    /// (`did:dverse:z...#keys-1`), into its components per the DID URL grammar.
+    pub fn parse(&self) -> Result<DidComponents> {
+        let did_url = self.trimmed();
+        let rest = did_url.strip_prefix("did:").ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID must start with \"did:\": {}", did_url))
+        })?;
 
-        if decoded_bytes.len() < Self::MULTICODEC_ED25519_PUB.len() || 
-           &decoded_bytes[0..Self::MULTICODEC_ED25519_PUB.len()] != Self::MULTICODEC_ED25519_PUB {
-            return Err(IdentityError::UnsupportedMulticodec(format!("Unsupported or invalid multicodec prefix: {:?}", &decoded_bytes[0..Self::MULTICODEC_ED25519_PUB.len()])));
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query.to_string())),
+            None => (rest, None),
+        };
+        let (did, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], Some(rest[index..].to_string())),
+            None => (rest, None),
+        };
+
+        let (method, method_specific_id) = did.split_once(':').ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID is missing a method-specific identifier: {}", did_url))
+        })?;
+        if method.is_empty() {
+            return Err(IdentityError::InvalidDidFormat(format!("DID method must not be empty: {}", did_url)));
         }
+        Self::validate_syntax(&format!("did:{}", did))?;
 
-        let public_key_bytes = decoded_bytes[Self::MULTICODEC_ED25519_PUB.len()..].to_vec();
+        Ok(DidComponents {
+            method: method.to_string(),
+            method_specific_id: method_specific_id.to_string(),
+            path,
+            query,
+            fragment,
+        })
+    }
 
-        Ok(PublicKey(public_key_bytes))
+    /// The Ed25519 key embedded in this DID, in any supported multibase (`did:dverse:z...`,
+    /// `did:dverse:u...`, ...). A DID whose multicodec names another key type,
+    /// including runtime-registered ones, is `UnsupportedMulticodec`; use
+    /// `to_typed_public_key` to read those.
+    pub fn to_public_key(&self) -> Result<PublicKey> {
+        Self::decode_public_key(self.encoded_key_part()?)
+    }
+
+    /// The embedded key together with the type named by its multicodec prefix. Unlike
+    /// `to_public_key` this also returns keys of runtime-registered types (see
+    /// `register_multicodec`); callers must check the type before using the bytes.
+    pub fn to_typed_public_key(&self) -> Result<(KeyType, PublicKey)> {
+        let decoded = Self::decode_key_material(self.encoded_key_part()?)?;
+        Ok((decoded.key_type, decoded.public_key))
+    }
+
+    // The stored string without surrounding whitespace; see `validate_syntax`.
+    fn trimmed(&self) -> &str {
+        self.0.trim()
+    }
+
+    // The multibase key of a `did:dverse` DID, after checking the DID's syntax.
+    fn encoded_key_part(&self) -> Result<&str> {
+        let did = self.trimmed();
+        Self::validate_syntax(did)?;
+        did.strip_prefix(Self::DID_DVERSE_PREFIX).ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID does not start with expected prefix: {}", did))
+        })
+    }
+
+    // Only Ed25519 keys: the bytes of any other type must never reach Ed25519 verification.
+    fn decode_public_key(encoded_part: &str) -> Result<PublicKey> {
+        let decoded = Self::decode_key_material(encoded_part)?;
+        if decoded.key_type != KeyType::Ed25519 {
+            return Err(IdentityError::UnsupportedMulticodec(format!(
+                "DID key is {:?}, expected Ed25519",
+                decoded.key_type
+            )));
+        }
+        Ok(decoded.public_key)
+    }
+
+    // Accepts any multibase; `Did::from_public_key` emits Base58Btc but forks using
+    // `DidConfig` may choose e.g. Base64Url.
+    fn decode_key_material(encoded_part: &str) -> Result<DecodedKey> {
+        let (_, decoded_bytes) = decode_multibase(encoded_part)?;
+
+        let (key_type, prefix_len) = multicodec::lookup(&decoded_bytes).ok_or_else(|| {
+            let prefix = &decoded_bytes[..decoded_bytes.len().min(Self::MULTICODEC_ED25519_PUB.len())];
+            IdentityError::UnsupportedMulticodec(format!("Unsupported or invalid multicodec prefix: {:?}", prefix))
+        })?;
+
+        let key_material = &decoded_bytes[prefix_len..];
+
+        // Dual-key DIDs append a multicodec-tagged X25519 key after the Ed25519 key.
+        let x25519_offset = 32 + Self::MULTICODEC_X25519_PUB.len();
+        if key_type == KeyType::Ed25519
+            && key_material.len() == x25519_offset + 32
+            && &key_material[32..x25519_offset] == Self::MULTICODEC_X25519_PUB
+        {
+            let mut key_agreement_key = [0u8; 32];
+            key_agreement_key.copy_from_slice(&key_material[x25519_offset..]);
+            return Ok(DecodedKey {
+                key_type,
+                public_key: PublicKey(key_material[..32].to_vec()),
+                key_agreement_key: Some(key_agreement_key),
+            });
+        }
+
+        Ok(DecodedKey { key_type, public_key: PublicKey(key_material.to_vec()), key_agreement_key: None })
+    }
+
+    /// The key type named by this DID's multicodec prefix, including runtime-registered
+    /// types (see `register_multicodec`).
+    pub fn key_type(&self) -> Result<KeyType> {
+        Ok(Self::decode_key_material(self.encoded_key_part()?)?.key_type)
+    }
+
+    /// Builds a DID advertising both the Ed25519 verification key and the X25519 key
+    /// agreement key derived from it (the Montgomery form of the same curve point).
+    pub fn from_keypair_with_encryption(keypair: &KeyPair) -> Result<Self> {
+        let key_agreement_key = verifying_key_from(&keypair.public_key)?.to_montgomery();
+
+        let mut prefixed_key_bytes = Vec::new();
+        prefixed_key_bytes.extend_from_slice(Self::MULTICODEC_ED25519_PUB);
+        prefixed_key_bytes.extend_from_slice(&keypair.public_key.0);
+        prefixed_key_bytes.extend_from_slice(Self::MULTICODEC_X25519_PUB);
+        prefixed_key_bytes.extend_from_slice(key_agreement_key.as_bytes());
+
+        let encoded_key = encode(Base::Base58Btc, &prefixed_key_bytes);
+
+        let did_string = format!("{}{}", Self::DID_DVERSE_PREFIX, encoded_key);
+        Self::validate_syntax(&did_string)?;
+
+        Ok(Did(did_string))
+    }
+
+    /// The Ed25519 verification key. Equivalent to `to_public_key`.
+    pub fn signing_key(&self) -> Result<PublicKey> {
+        self.to_public_key()
+    }
+
+    /// Verifies `signature` over `message` against the key embedded in this DID, with no
+    /// private key material involved.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.to_public_key()?.verify(message, signature)
+    }
+
+    /// Expands the embedded key into this DID's document without going through a
+    /// `DidResolver`. Only `did:dverse` is key-based in this crate; other methods return
+    /// `InvalidDidFormat`.
+    #[cfg(feature = "std")]
+    pub fn resolve_local(&self) -> Result<DidDocument> {
+        let did = self.trimmed();
+        Self::validate_syntax(did)?;
+        resolver::key_document(&Did(did.to_string()))
+    }
+
+    /// The W3C DID Document for this DID: the embedded Ed25519 key as `<did>#keys-1`, used
+    /// for both `authentication` and `assertionMethod`, and for DIDs built with
+    /// `from_keypair_with_encryption` the X25519 key as `<did>#keys-2` under `keyAgreement`.
+    /// Same as `resolve_local`.
+    #[cfg(feature = "std")]
+    pub fn to_did_document(&self) -> Result<DidDocument> {
+        self.resolve_local()
+    }
+
+    /// The X25519 key agreement key of a DID built with `from_keypair_with_encryption`.
+    pub fn key_agreement_key(&self) -> Result<[u8; 32]> {
+        Self::decode_key_material(self.encoded_key_part()?)?.key_agreement_key.ok_or_else(|| {
+            IdentityError::UnsupportedMulticodec(format!("DID does not carry an X25519 key agreement key: {}", self.0))
+        })
+    }
+
+    /// The same identity, under the same method, with its key material re-encoded in
+    /// `base`. Dual-key DIDs keep their X25519 key. Bases that produce characters a DID
+    /// cannot contain (e.g. padded Base64) return `InvalidDidFormat`.
+    pub fn reencode(&self, base: Base) -> Result<Did> {
+        let did = self.trimmed();
+        Self::validate_syntax(did)?;
+        let (method, encoded_part) = did["did:".len()..]
+            .split_once(':')
+            .ok_or_else(|| IdentityError::InvalidDidFormat(format!("DID is missing a method-specific identifier: {}", did)))?;
+        let (_, key_material) = decode_multibase(encoded_part)?;
+        let did_string = format!("did:{}:{}", method, encode(base, key_material));
+        Self::validate_syntax(&did_string)?;
+        Ok(Did(did_string))
+    }
+
+    /// This identity re-encoded in every multibase that yields a valid DID, starting with
+    /// the default Base58Btc. The method is kept, and every entry decodes to the same key.
+    pub fn available_encodings(&self) -> Result<Vec<(Base, Did)>> {
+        Self::DID_SAFE_BASES.iter().map(|&base| Ok((base, self.reencode(base)?))).collect()
     }
 
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The compact binary identity: the 2-byte Ed25519 multicodec prefix followed by the
+    /// 32-byte public key.
+    pub fn to_binary(&self) -> Result<[u8; 34]> {
+        let public_key = self.to_public_key()?;
+        if public_key.0.len() != 32 {
+            return Err(IdentityError::InvalidKey(format!(
+                "Public key must be 32 bytes, got {}",
+                public_key.0.len()
+            )));
+        }
+
+        let mut binary = [0u8; 34];
+        binary[..2].copy_from_slice(Self::MULTICODEC_ED25519_PUB);
+        binary[2..].copy_from_slice(&public_key.0);
+        Ok(binary)
+    }
+
+    pub fn from_binary(binary: &[u8; 34]) -> Result<Did> {
+        if &binary[..2] != Self::MULTICODEC_ED25519_PUB {
+            return Err(IdentityError::UnsupportedMulticodec(format!(
+                "Unsupported or invalid multicodec prefix: {:?}",
+                &binary[..2]
+            )));
+        }
+        Self::from_public_key(&PublicKey(binary[2..].to_vec()))
+    }
+
+    /// Assigns this identity to one of `num_buckets` shards. The bucket is derived from a
+    /// hash of the decoded key bytes, so it is stable across processes and DID encodings.
+    pub fn shard(&self, num_buckets: u32) -> Result<u32> {
+        if num_buckets == 0 {
+            return Err(IdentityError::InvalidArgument("Number of shard buckets must be non-zero".to_string()));
+        }
+        let public_key = self.to_public_key()?;
+
+        let digest = Sha256::new()
+            .chain_update(Self::SHARD_DOMAIN)
+            .chain_update(public_key.as_bytes())
+            .finalize();
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        Ok((u64::from_be_bytes(prefix) % num_buckets as u64) as u32)
+    }
+
+    /// Returns true if both DIDs encode the same key, ignoring an optional network segment
+    /// (`did:dverse:testnet:z...` and `did:dverse:z...` compare equal for the same key).
+    pub fn same_key_any_network(&self, other: &Did) -> Result<bool> {
+        Ok(self.public_key_any_network()? == other.public_key_any_network()?)
+    }
+
+    fn public_key_any_network(&self) -> Result<PublicKey> {
+        let did = self.trimmed();
+        Self::validate_syntax(did)?;
+        let method_specific_id = did.strip_prefix(Self::DID_DVERSE_PREFIX).ok_or_else(|| {
+            IdentityError::InvalidDidFormat(format!("DID does not start with expected prefix: {}", did))
+        })?;
+
+        let encoded_part = match method_specific_id.split_once(':') {
+            Some((network, encoded_part)) => {
+                if network.is_empty() || !network.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                    return Err(IdentityError::InvalidDidFormat(format!("Invalid network segment in DID: {}", did)));
+                }
+                encoded_part
+            }
+            None => method_specific_id,
+        };
+
+        Self::decode_public_key(encoded_part)
+    }
+
+    /// Returns a stable 16-byte seed for identicon/avatar generation. The seed is derived
+    /// from the decoded key bytes, so it does not depend on how the DID string is encoded.
+    pub fn avatar_seed(&self) -> Result<[u8; 16]> {
+        let public_key = self.to_public_key()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(Self::AVATAR_SEED_DOMAIN);
+        hasher.update(public_key.as_bytes());
+        let digest = hasher.finalize();
+
+        let mut seed = [0u8; 16];
+        seed.copy_from_slice(&digest[..16]);
+        Ok(seed)
+    }
+
+    /// Returns a stable RGB accent color for this identity. Like `avatar_seed` it is derived
+    /// from the decoded key bytes. The hue spans the full wheel, while saturation (55-80%)
+    /// and lightness (40-55%) are kept in a range that stays readable on light and dark
+    /// backgrounds.
+    pub fn theme_color(&self) -> Result<[u8; 3]> {
+        let public_key = self.to_public_key()?;
+        let digest = Sha256::new()
+            .chain_update(Self::THEME_COLOR_DOMAIN)
+            .chain_update(public_key.as_bytes())
+            .finalize();
+
+        let hue = f64::from(u16::from_be_bytes([digest[0], digest[1]]) % 360);
+        let saturation = 0.55 + 0.25 * f64::from(digest[2]) / 255.0;
+        let lightness = 0.40 + 0.15 * f64::from(digest[3]) / 255.0;
+        Ok(hsl_to_rgb(hue, saturation, lightness))
+    }
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let offset = lightness - chroma / 2.0;
+    // Channels are non-negative, so adding 0.5 and truncating rounds like `f64::round`,
+    // which is not available without `std`.
+    let channel = |value: f64| ((value + offset) * 255.0 + 0.5) as u8;
+    [channel(r), channel(g), channel(b)]
 }
 
 // --- Conversions for convenience ---
+impl From<KeyPair> for PublicKey {
+    fn from(keypair: KeyPair) -> Self {
+        keypair.public_key
+    }
+}
+
+impl From<&KeyPair> for PublicKey {
+    fn from(keypair: &KeyPair) -> Self {
+        keypair.public_key.clone()
+    }
+}
+
+/// Checked parsing with the same validation as `Did::new`, so `"did:dverse:z...".parse()?`
+/// works in functions returning `Result`.
+impl core::str::FromStr for Did {
+    type Err = IdentityError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Did::new(s)
+    }
+}
+
+/// Unchecked: wraps any string, valid DID or not. Use `Did::new` to validate input.
 impl From<String> for Did {
     fn from(s: String) -> Self {
         Did(s)
     }
 }
 
+/// Unchecked: wraps any string, valid DID or not. Use `Did::new` to validate input.
 impl From<&str> for Did {
     fn from(s: &str) -> Self {
         Did(s.to_string())
     }
 }
 
-impl std::fmt::Display for Did {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+// Byte-wise comparison against string DIDs, so incoming DIDs can be checked against
+// hardcoded constants (e.g. `const ADMINS: &[&str]`) without allocating a `Did`.
+impl PartialEq<str> for Did {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Did {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Did> for str {
+    fn eq(&self, other: &Did) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<Did> for &str {
+    fn eq(&self, other: &Did) -> bool {
+        *self == other.0
+    }
+}
+
+/// Parses the 34-byte binary form produced by `Did::to_binary`.
+impl TryFrom<&[u8]> for Did {
+    type Error = IdentityError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let binary: &[u8; 34] = bytes.try_into().map_err(|_| {
+            IdentityError::ArrayConversionError(format!("Binary DID must be 34 bytes, got {}", bytes.len()))
+        })?;
+        Did::from_binary(binary)
+    }
+}
+
+impl core::fmt::Display for Did {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -0,0 +1,70 @@
+// Token-bucket guard for endpoints that verify attacker-supplied signatures.
+
+use std::time::Instant;
+
+use crate::clock::{MonotonicClock, SystemClock};
+use crate::{IdentityError, PublicKey, Result};
+
+/// Wraps verification in a token bucket so a flood of requests cannot exhaust the CPU.
+/// Each `try_verify` spends one token before any cryptographic work is done; tokens
+/// refill continuously at `refill_per_second` up to `capacity`, measured on a monotonic
+/// clock so wall clock adjustments neither grant nor withhold tokens.
+#[derive(Debug, Clone)]
+pub struct VerificationLimiter<C: MonotonicClock = SystemClock> {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+    clock: C,
+}
+
+impl VerificationLimiter {
+    /// A limiter that starts full, allowing a burst of `capacity` verifications. A
+    /// `refill_per_second` that is negative, NaN or infinite is `InvalidArgument`.
+    pub fn new(capacity: u32, refill_per_second: f64) -> Result<Self> {
+        VerificationLimiter::with_clock(capacity, refill_per_second, SystemClock)
+    }
+}
+
+impl<C: MonotonicClock> VerificationLimiter<C> {
+    pub fn with_clock(capacity: u32, refill_per_second: f64, clock: C) -> Result<Self> {
+        // A NaN rate would poison the token count and let every call through.
+        if !refill_per_second.is_finite() || refill_per_second < 0.0 {
+            return Err(IdentityError::InvalidArgument(format!(
+                "Refill rate must be a finite, non-negative number, got {}",
+                refill_per_second
+            )));
+        }
+        Ok(VerificationLimiter {
+            capacity: f64::from(capacity),
+            refill_per_second,
+            tokens: f64::from(capacity),
+            last_refill: clock.instant(),
+            clock,
+        })
+    }
+
+    /// Tokens currently available, rounded down.
+    pub fn available(&mut self) -> u32 {
+        self.refill();
+        self.tokens as u32
+    }
+
+    fn refill(&mut self) {
+        let now = self.clock.instant();
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Verifies `signature` if the budget allows, otherwise returns
+    /// `IdentityError::RateLimited` without touching the signature.
+    pub fn try_verify(&mut self, public_key: &PublicKey, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.refill();
+        if self.tokens < 1.0 {
+            return Err(IdentityError::RateLimited("Verification budget exhausted".to_string()));
+        }
+        self.tokens -= 1.0;
+        crate::verify_with_public_key(public_key, message, signature)
+    }
+}
@@ -0,0 +1,150 @@
+// BIP-39 mnemonic backups (https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki).
+//
+// The 32-byte private key seed is the mnemonic's entropy, so a key pair round-trips
+// through a 24-word phrase. A non-empty passphrase (the BIP-39 "25th word") instead
+// selects the SLIP-0010 Ed25519 master key of the standard BIP-39 seed, giving a separate
+// "hidden" identity per passphrase, the same one a SLIP-0010 wallet derives.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use crate::{IdentityError, KeyPair, Result};
+
+// The official English wordlist (SHA-256 2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda).
+const ENGLISH_WORDLIST: &str = include_str!("bip39_english.txt");
+const PBKDF2_ROUNDS: u32 = 2048;
+const SLIP10_MASTER_KEY: &[u8] = b"ed25519 seed";
+
+fn wordlist() -> impl Iterator<Item = &'static str> {
+    ENGLISH_WORDLIST.lines()
+}
+
+fn word_at(index: usize) -> &'static str {
+    wordlist().nth(index).expect("BIP-39 wordlist has 2048 entries")
+}
+
+fn word_index(word: &str) -> Option<usize> {
+    wordlist().position(|candidate| candidate == word)
+}
+
+fn entropy_to_phrase(entropy: &[u8]) -> String {
+    let checksum_bits = entropy.len() / 4;
+    let checksum = Sha256::digest(entropy);
+    let bit = |i: usize| {
+        let byte = if i < entropy.len() * 8 { entropy[i / 8] } else { checksum[(i / 8) - entropy.len()] };
+        (byte >> (7 - i % 8)) & 1
+    };
+
+    let word_count = (entropy.len() * 8 + checksum_bits) / 11;
+    (0..word_count)
+        .map(|w| word_at((0..11).fold(0usize, |index, b| (index << 1) | bit(w * 11 + b) as usize)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Decodes a 12 to 24 word phrase back to its entropy, checking the checksum.
+fn phrase_to_entropy(phrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err(IdentityError::InvalidKey(format!(
+            "Mnemonic must have 12, 15, 18, 21 or 24 words, got {}",
+            words.len()
+        )));
+    }
+
+    let mut bits = Zeroizing::new(Vec::with_capacity(words.len() * 11));
+    for word in &words {
+        let index = word_index(word)
+            .ok_or_else(|| IdentityError::InvalidKey(format!("Unknown mnemonic word: {:?}", word)))?;
+        bits.extend((0..11).rev().map(|b| ((index >> b) & 1) as u8));
+    }
+
+    let entropy_bits = words.len() * 11 * 32 / 33;
+    let entropy: Zeroizing<Vec<u8>> =
+        Zeroizing::new(bits[..entropy_bits].chunks(8).map(|byte| byte.iter().fold(0u8, |acc, b| (acc << 1) | b)).collect());
+    let checksum = Sha256::digest(&*entropy);
+    let checksum_ok = bits[entropy_bits..]
+        .iter()
+        .enumerate()
+        .all(|(i, b)| (checksum[i / 8] >> (7 - i % 8)) & 1 == *b);
+    if !checksum_ok {
+        return Err(IdentityError::InvalidKey("Mnemonic checksum does not match".to_string()));
+    }
+    Ok(entropy)
+}
+
+fn hmac_sha512(key: &[u8]) -> Result<Hmac<Sha512>> {
+    Hmac::<Sha512>::new_from_slice(key).map_err(|e| IdentityError::KeyGenerationError(e.to_string()))
+}
+
+// PBKDF2-HMAC-SHA512 with salt "mnemonic" + passphrase. BIP-39 asks for NFKD
+// normalization; the English words are ASCII, and the passphrase is used as given.
+fn phrase_to_seed(phrase: &str, passphrase: &str) -> Result<Zeroizing<[u8; 64]>> {
+    let normalized = Zeroizing::new(phrase.split_whitespace().collect::<Vec<_>>().join(" "));
+    let prf = hmac_sha512(normalized.as_bytes())?;
+
+    let mut block = prf.clone();
+    block.update(b"mnemonic");
+    block.update(passphrase.as_bytes());
+    block.update(&1u32.to_be_bytes());
+    let mut u = Zeroizing::new([0u8; 64]);
+    u.copy_from_slice(&block.finalize().into_bytes());
+
+    let mut seed = Zeroizing::new(*u);
+    for _ in 1..PBKDF2_ROUNDS {
+        let mut round = prf.clone();
+        round.update(&*u);
+        u.copy_from_slice(&round.finalize().into_bytes());
+        seed.iter_mut().zip(u.iter()).for_each(|(s, x)| *s ^= x);
+    }
+    Ok(seed)
+}
+
+// The key seed for `entropy` under `passphrase`. The empty passphrase leaves the entropy
+// as is, which is what lets `to_mnemonic` round-trip; any other passphrase selects the
+// SLIP-0010 master key of the BIP-39 seed (the left half of HMAC-SHA512("ed25519 seed", seed)).
+fn key_seed(phrase: &str, entropy: &[u8], passphrase: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    if passphrase.is_empty() {
+        key.copy_from_slice(entropy);
+    } else {
+        let seed = phrase_to_seed(phrase, passphrase)?;
+        let mut master = hmac_sha512(SLIP10_MASTER_KEY)?;
+        master.update(&*seed);
+        key.copy_from_slice(&master.finalize().into_bytes()[..32]);
+    }
+    Ok(key)
+}
+
+impl KeyPair {
+    /// Encodes the 32-byte private key seed as a 24-word BIP-39 English mnemonic.
+    pub fn to_mnemonic(&self) -> Result<String> {
+        let seed = self.private_key.as_bytes();
+        if seed.len() != 32 {
+            return Err(IdentityError::InvalidKey(format!("Private key must be 32 bytes, got {}", seed.len())));
+        }
+        Ok(entropy_to_phrase(seed))
+    }
+
+    /// Restores a key pair from a 24-word BIP-39 English mnemonic, rejecting other
+    /// lengths, unknown words and bad checksums with `InvalidKey`.
+    ///
+    /// With an empty `passphrase` the mnemonic's entropy is the private key seed, as
+    /// written by `to_mnemonic`. A non-empty passphrase derives the BIP-39 seed
+    /// (PBKDF2-HMAC-SHA512, salt "mnemonic" + passphrase) and uses its SLIP-0010 Ed25519
+    /// master key, so every passphrase yields a different key pair from the same phrase,
+    /// and the result equals `ExtendedKeyPair::from_seed` on a wallet's BIP-39 seed.
+    /// Non-ASCII passphrases must already be NFKD-normalized.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self> {
+        let entropy = phrase_to_entropy(phrase)?;
+        if entropy.len() != 32 {
+            return Err(IdentityError::InvalidKey(format!(
+                "Mnemonic must have 24 words, got {}",
+                phrase.split_whitespace().count()
+            )));
+        }
+        let seed = key_seed(phrase, &entropy, passphrase)?;
+        KeyPair::from_seed(&seed)
+    }
+}
@@ -0,0 +1,99 @@
+// Multicodec key-type table, extensible at runtime.
+//
+// Built-in codes are fixed. Additional codes can be registered at runtime for
+// experimental or private key types; the table is a process-wide `RwLock`, so
+// registration and lookup are safe from any thread. The runtime table needs `std`;
+// without it only the built-in codes are recognized.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::{OnceLock, RwLock};
+
+#[cfg(feature = "std")]
+use crate::{IdentityError, Result};
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    Ed25519,
+    /// A runtime-registered key type, identified by name.
+    Custom(String),
+}
+
+// Multicodec for Ed25519 public keys (0xed01)
+pub(crate) const ED25519_PUB: &[u8] = &[0xed, 0x01];
+// Multicodec for X25519 public keys (0xec01)
+pub(crate) const X25519_PUB: &[u8] = &[0xec, 0x01];
+
+const BUILT_IN: &[(&[u8], KeyType)] = &[(ED25519_PUB, KeyType::Ed25519)];
+
+// X25519 is reserved for key-agreement keys embedded in dual-key DIDs.
+#[cfg(feature = "std")]
+const RESERVED: &[&[u8]] = &[ED25519_PUB, X25519_PUB];
+
+#[cfg(feature = "std")]
+fn registry() -> &'static RwLock<HashMap<Vec<u8>, KeyType>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<Vec<u8>, KeyType>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers `code` as the multicodec prefix for `key_type`, so DIDs using it are
+/// recognized by `Did::key_type` and `Did::to_public_key`. Built-in codes cannot be
+/// overridden, and a code can only be bound to one key type. Re-registering the same
+/// pair is a no-op.
+#[cfg(feature = "std")]
+pub fn register_multicodec(code: &[u8], key_type: KeyType) -> Result<()> {
+    if code.is_empty() {
+        return Err(IdentityError::InvalidArgument("Multicodec code must not be empty".to_string()));
+    }
+    if RESERVED.iter().any(|reserved| reserved.starts_with(code) || code.starts_with(reserved)) {
+        return Err(IdentityError::UnsupportedMulticodec(format!(
+            "Multicodec {:02x?} overlaps a built-in code and cannot be registered",
+            code
+        )));
+    }
+    if key_type == KeyType::Ed25519 {
+        return Err(IdentityError::InvalidArgument("Ed25519 is a built-in key type".to_string()));
+    }
+
+    let mut table = registry().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((existing_code, existing)) = table
+        .iter()
+        .find(|(existing_code, _)| existing_code.starts_with(code) || code.starts_with(existing_code))
+    {
+        if existing_code.as_slice() == code && *existing == key_type {
+            return Ok(());
+        }
+        return Err(IdentityError::UnsupportedMulticodec(format!(
+            "Multicodec {:02x?} conflicts with registered code {:02x?} ({:?})",
+            code, existing_code, existing
+        )));
+    }
+    table.insert(code.to_vec(), key_type);
+    Ok(())
+}
+
+/// Finds the key type whose multicodec prefixes `bytes`, returning it with the prefix length.
+pub(crate) fn lookup(bytes: &[u8]) -> Option<(KeyType, usize)> {
+    if let Some((code, key_type)) = BUILT_IN.iter().find(|(code, _)| bytes.starts_with(code)) {
+        return Some((key_type.clone(), code.len()));
+    }
+    lookup_registered(bytes)
+}
+
+#[cfg(feature = "std")]
+fn lookup_registered(bytes: &[u8]) -> Option<(KeyType, usize)> {
+    let table = registry().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    table
+        .iter()
+        .find(|(code, _)| bytes.starts_with(code))
+        .map(|(code, key_type)| (key_type.clone(), code.len()))
+}
+
+#[cfg(not(feature = "std"))]
+fn lookup_registered(_bytes: &[u8]) -> Option<(KeyType, usize)> {
+    None
+}
@@ -0,0 +1,96 @@
+// Server-side replay protection for challenge nonces, and duplicate detection for
+// signed submissions.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+use crate::clock::{self, Clock, SystemClock};
+use crate::{IdentityError, Result, Signature};
+
+/// Records nonces that have already been used so a challenge can only be answered once.
+pub trait NonceStore {
+    /// Marks `nonce` as used. Returns `IdentityError::ReplayDetected` if it was seen before.
+    fn consume(&mut self, nonce: &[u8]) -> Result<()>;
+}
+
+/// In-memory `NonceStore` that remembers each nonce for a fixed time-to-live. Expired
+/// entries are purged on every `consume`, so memory stays bounded by the request rate.
+#[derive(Debug, Clone)]
+pub struct InMemoryNonceStore<C: Clock = SystemClock> {
+    ttl: Duration,
+    seen: HashMap<Vec<u8>, SystemTime>,
+    clock: C,
+}
+
+impl InMemoryNonceStore {
+    pub fn new(ttl: Duration) -> Self {
+        InMemoryNonceStore::with_clock(ttl, SystemClock)
+    }
+}
+
+impl<C: Clock> InMemoryNonceStore<C> {
+    pub fn with_clock(ttl: Duration, clock: C) -> Self {
+        InMemoryNonceStore { ttl, seen: HashMap::new(), clock }
+    }
+
+    pub fn purge_expired(&mut self) {
+        let now = self.clock.now();
+        let ttl = self.ttl;
+        self.seen.retain(|_, first_seen| clock::elapsed(*first_seen, now) < ttl);
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+impl<C: Clock> NonceStore for InMemoryNonceStore<C> {
+    fn consume(&mut self, nonce: &[u8]) -> Result<()> {
+        self.purge_expired();
+        if self.seen.contains_key(nonce) {
+            return Err(IdentityError::ReplayDetected("Nonce has already been used".to_string()));
+        }
+        self.seen.insert(nonce.to_vec(), self.clock.now());
+        Ok(())
+    }
+}
+
+/// Remembers signatures that have already been processed, so a request handler can treat
+/// a resubmitted request as a duplicate and stay idempotent. Entries are kept until
+/// `clear`; unlike `InMemoryNonceStore` there is no expiry.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureDedup {
+    seen: HashSet<Signature>,
+}
+
+impl SignatureDedup {
+    pub fn new() -> Self {
+        SignatureDedup::default()
+    }
+
+    /// Records `signature`, returning `true` if it had already been recorded (a duplicate)
+    /// and `false` the first time it is seen.
+    pub fn check_and_record(&mut self, signature: &Signature) -> bool {
+        !self.seen.insert(*signature)
+    }
+
+    pub fn contains(&self, signature: &Signature) -> bool {
+        self.seen.contains(signature)
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
@@ -0,0 +1,183 @@
+// Reusable signing configurations.
+//
+// A `SigningPolicy` bundles the options the individual helpers expose separately (domain
+// tag, audience binding, canonicalization and a context) so they are chosen once and
+// applied the same way on both sides. Every policy, including an empty one, signs
+//
+//   SignatureDomain::Custom("signing-policy/v1") framing of
+//       fields(canonicalization, domain, audience, context, canonicalize(message))
+//
+// with plain Ed25519, where `fields` is the length-prefixed encoding `sign_fields` uses,
+// canonicalization is `raw` or `json`, and each optional setting is `0x00` when unset or
+// `0x01 || value` when set. Policy signatures therefore never verify as `KeyPair::sign`
+// signatures or under a policy that differs in any setting.
+
+use serde_json::Value;
+
+use crate::signer::HardwareSigner;
+use crate::{encode_fields, IdentityError, PublicKey, Result, SignatureDomain};
+
+const POLICY_DOMAIN: SignatureDomain<'static> = SignatureDomain::Custom(b"signing-policy/v1");
+
+/// How the message is normalized before signing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Canonicalization {
+    /// The bytes are signed as given.
+    #[default]
+    Raw,
+    /// The message must be JSON; it is re-serialized compactly with object keys sorted,
+    /// so whitespace and key order do not affect the signature. Numbers keep serde_json's
+    /// formatting, so this is close to, but not a full implementation of, RFC 8785 (JCS).
+    Json,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SigningPolicy {
+    domain: Option<Vec<u8>>,
+    audience: Option<String>,
+    canonicalization: Canonicalization,
+    context: Option<Vec<u8>>,
+}
+
+/// Signs messages with one key under a `SigningPolicy`.
+pub struct PolicySigner<'a, S: HardwareSigner + ?Sized> {
+    policy: &'a SigningPolicy,
+    signer: &'a S,
+}
+
+/// Verifies signatures made under the same `SigningPolicy`.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyVerifier<'a> {
+    policy: &'a SigningPolicy,
+    public_key: &'a PublicKey,
+}
+
+impl<S: HardwareSigner + ?Sized> Clone for PolicySigner<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: HardwareSigner + ?Sized> Copy for PolicySigner<'_, S> {}
+
+impl<S: HardwareSigner + ?Sized> std::fmt::Debug for PolicySigner<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicySigner").field("policy", self.policy).finish_non_exhaustive()
+    }
+}
+
+// `0x00` when unset, `0x01 || value` when set, so an unset option never matches an empty one.
+fn optional_field(value: Option<&[u8]>) -> Vec<u8> {
+    match value {
+        Some(value) => [&[1u8][..], value].concat(),
+        None => vec![0],
+    }
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) -> Result<()> {
+    match value {
+        Value::Object(members) => {
+            let mut keys: Vec<&String> = members.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key).map_err(|e| IdentityError::EncodingError(e.to_string()))?);
+                out.push(':');
+                write_canonical_json(&members[key], out)?;
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out)?;
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&serde_json::to_string(scalar).map_err(|e| IdentityError::EncodingError(e.to_string()))?),
+    }
+    Ok(())
+}
+
+impl SigningPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Separates signatures by an application-chosen domain tag.
+    pub fn domain(mut self, domain: impl Into<Vec<u8>>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Binds signatures to an intended recipient, e.g. a verifier's DID.
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    pub fn canonicalization(mut self, canonicalization: Canonicalization) -> Self {
+        self.canonicalization = canonicalization;
+        self
+    }
+
+    /// Binds signatures to an application context, e.g. a protocol version.
+    pub fn context(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// A signer for any `HardwareSigner`, including a `KeyPair`.
+    pub fn signer<'a, S: HardwareSigner + ?Sized>(&'a self, signer: &'a S) -> PolicySigner<'a, S> {
+        PolicySigner { policy: self, signer }
+    }
+
+    pub fn verifier<'a>(&'a self, public_key: &'a PublicKey) -> PolicyVerifier<'a> {
+        PolicyVerifier { policy: self, public_key }
+    }
+
+    // The bytes handed to Ed25519, per the module docs.
+    fn signed_bytes(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let payload = match self.canonicalization {
+            Canonicalization::Raw => message.to_vec(),
+            Canonicalization::Json => {
+                let value: Value =
+                    serde_json::from_slice(message).map_err(|e| IdentityError::DecodingError(e.to_string()))?;
+                let mut canonical = String::new();
+                write_canonical_json(&value, &mut canonical)?;
+                canonical.into_bytes()
+            }
+        };
+
+        let canonicalization: &[u8] = match self.canonicalization {
+            Canonicalization::Raw => b"raw",
+            Canonicalization::Json => b"json",
+        };
+        let fields = encode_fields(&[
+            canonicalization,
+            &optional_field(self.domain.as_deref()),
+            &optional_field(self.audience.as_deref().map(str::as_bytes)),
+            &optional_field(self.context.as_deref()),
+            &payload,
+        ]);
+        Ok(POLICY_DOMAIN.tagged_message(&fields))
+    }
+}
+
+impl<S: HardwareSigner + ?Sized> PolicySigner<'_, S> {
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.signer.sign(&self.policy.signed_bytes(message)?)
+    }
+}
+
+impl PolicyVerifier<'_> {
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        crate::verify_with_public_key(self.public_key, &self.policy.signed_bytes(message)?, signature)
+    }
+}
@@ -0,0 +1,96 @@
+// Registration statements: a DID disclosed together with proof of control over it.
+//
+// A server hands out a nonce, the client answers with a statement naming its DID, the
+// nonce and the current time, signed by the DID's key. One verification then tells the
+// server both who is registering and that they hold the key.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{Clock, SystemClock};
+use crate::{Did, IdentityError, KeyPair, Result, SignatureDomain};
+
+const REGISTRATION_DOMAIN: SignatureDomain<'static> = SignatureDomain::Custom(b"registration-statement/v1");
+
+/// How long after its timestamp a statement is still accepted.
+pub const REGISTRATION_MAX_AGE: Duration = Duration::from_secs(300);
+/// How far in the future a statement's timestamp may be, to tolerate client clock skew.
+pub const REGISTRATION_MAX_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationStatement {
+    pub did: Did,
+    pub nonce: Vec<u8>,
+    /// Unix timestamp (seconds) at which the statement was made.
+    pub timestamp: u64,
+    /// Signature over the DID, nonce and timestamp in the registration domain.
+    pub signature: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct SigningPayload<'a> {
+    did: &'a Did,
+    nonce: &'a [u8],
+    timestamp: u64,
+}
+
+fn signing_bytes(did: &Did, nonce: &[u8], timestamp: u64) -> Result<Vec<u8>> {
+    serde_json::to_vec(&SigningPayload { did, nonce, timestamp }).map_err(|e| IdentityError::EncodingError(e.to_string()))
+}
+
+fn unix_seconds(time: SystemTime) -> Result<u64> {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .map_err(|e| IdentityError::InvalidArgument(format!("Time is before the Unix epoch: {}", e)))
+}
+
+impl KeyPair {
+    /// A statement disclosing this key pair's DID and proving control of it by signing the
+    /// server-issued `nonce` together with the current time.
+    pub fn register_statement(&self, nonce: &[u8]) -> Result<RegistrationStatement> {
+        self.register_statement_with_clock(nonce, &SystemClock)
+    }
+
+    /// `register_statement` timestamped by `clock` instead of the system time.
+    pub fn register_statement_with_clock(&self, nonce: &[u8], clock: &impl Clock) -> Result<RegistrationStatement> {
+        let did = Did::from_public_key(&self.public_key)?;
+        let timestamp = unix_seconds(clock.now())?;
+        let signature = REGISTRATION_DOMAIN.sign_with(self, &signing_bytes(&did, nonce, timestamp)?)?;
+        Ok(RegistrationStatement { did, nonce: nonce.to_vec(), timestamp, signature })
+    }
+}
+
+/// Checks that `statement` answers `expected_nonce`, was made within
+/// `REGISTRATION_MAX_AGE` of `now` (allowing `REGISTRATION_MAX_CLOCK_SKEW` into the future),
+/// and is signed by the key in its DID. Returns the registered DID. A wrong nonce or
+/// timestamp is `InvalidCredential`. Callers should also consume the nonce, e.g. with a
+/// `NonceStore`, so a statement cannot be replayed within its validity window.
+pub fn verify_registration(statement: &RegistrationStatement, expected_nonce: &[u8], now: SystemTime) -> Result<Did> {
+    if statement.nonce != expected_nonce {
+        return Err(IdentityError::InvalidCredential("Registration statement answers a different nonce".to_string()));
+    }
+
+    let now = unix_seconds(now)?;
+    if statement.timestamp > now.saturating_add(REGISTRATION_MAX_CLOCK_SKEW.as_secs()) {
+        return Err(IdentityError::InvalidCredential(format!(
+            "Registration statement is dated {}, in the future of {}",
+            statement.timestamp, now
+        )));
+    }
+    if now.saturating_sub(statement.timestamp) > REGISTRATION_MAX_AGE.as_secs() {
+        return Err(IdentityError::InvalidCredential(format!(
+            "Registration statement from {} expired at {}",
+            statement.timestamp,
+            statement.timestamp.saturating_add(REGISTRATION_MAX_AGE.as_secs())
+        )));
+    }
+
+    let payload = signing_bytes(&statement.did, &statement.nonce, statement.timestamp)?;
+    crate::verify_with_public_key(
+        &statement.did.to_public_key()?,
+        &REGISTRATION_DOMAIN.tagged_message(&payload),
+        &statement.signature,
+    )?;
+    Ok(statement.did.clone())
+}
@@ -0,0 +1,189 @@
+// DID resolution following the DID Core resolution result shape
+// (https://www.w3.org/TR/did-core/#did-resolution).
+
+use serde::{Deserialize, Serialize};
+
+use crate::document::{DidDocumentBuilder, VerificationMethod};
+use crate::{Did, DidDocument, IdentityError, KeyAgreementPublicKey, KeyType, PublicKey, Result};
+
+pub const DID_LD_JSON_CONTENT_TYPE: &str = "application/did+ld+json";
+
+// Standard resolution error codes.
+pub const ERROR_INVALID_DID: &str = "invalidDid";
+pub const ERROR_METHOD_NOT_SUPPORTED: &str = "methodNotSupported";
+pub const ERROR_NOT_FOUND: &str = "notFound";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResult {
+    pub did_document: Option<DidDocument>,
+    pub did_document_metadata: DocumentMetadata,
+    pub did_resolution_metadata: ResolutionMetadata,
+}
+
+impl ResolutionResult {
+    pub fn success(document: DidDocument, document_metadata: DocumentMetadata) -> Self {
+        ResolutionResult {
+            did_document: Some(document),
+            did_document_metadata: document_metadata,
+            did_resolution_metadata: ResolutionMetadata {
+                content_type: Some(DID_LD_JSON_CONTENT_TYPE.to_string()),
+                error: None,
+            },
+        }
+    }
+
+    pub fn error(code: &str) -> Self {
+        ResolutionResult {
+            did_document: None,
+            did_document_metadata: DocumentMetadata::default(),
+            did_resolution_metadata: ResolutionMetadata { content_type: None, error: Some(code.to_string()) },
+        }
+    }
+
+    /// Returns the resolved document, turning a resolution error code into an
+    /// `IdentityError::ResolutionError`.
+    pub fn into_document(self) -> Result<DidDocument> {
+        match (self.did_document, self.did_resolution_metadata.error) {
+            (Some(document), None) => Ok(document),
+            (_, Some(code)) => Err(IdentityError::ResolutionError(code)),
+            (None, None) => Err(IdentityError::ResolutionError(ERROR_NOT_FOUND.to_string())),
+        }
+    }
+}
+
+/// Resolves a DID to its DID Document. Failures are reported through the resolution
+/// metadata `error` code, as DID Core specifies, rather than as a Rust error.
+pub trait DidResolver {
+    fn resolve(&self, did: &Did) -> ResolutionResult;
+}
+
+/// Resolves `did:dverse` identifiers without any network access by expanding the key
+/// embedded in the DID into a document.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalResolver;
+
+impl DidResolver for LocalResolver {
+    fn resolve(&self, did: &Did) -> ResolutionResult {
+        if Did::validate_syntax(did.as_str()).is_err() {
+            return ResolutionResult::error(ERROR_INVALID_DID);
+        }
+        if !did.as_str().starts_with(Did::DID_DVERSE_PREFIX) {
+            return ResolutionResult::error(ERROR_METHOD_NOT_SUPPORTED);
+        }
+        match key_document(did) {
+            Ok(document) => ResolutionResult::success(document, DocumentMetadata::default()),
+            Err(_) => ResolutionResult::error(ERROR_INVALID_DID),
+        }
+    }
+}
+
+// Resolves `did` and returns its Ed25519 assertion method keys in document order.
+fn resolve_assertion_keys<R: DidResolver>(resolver: &R, did: &Did) -> Result<Vec<PublicKey>> {
+    let document = resolver.resolve(did).into_document()?;
+    if document.id != did.as_str() {
+        return Err(IdentityError::InvalidDocument(format!(
+            "Resolved document {} does not describe {}",
+            document.id, did
+        )));
+    }
+
+    let mut keys = Vec::new();
+    for method_id in &document.assertion_method {
+        let method = document.verification_method.iter().find(|m| &m.id == method_id).ok_or_else(|| {
+            IdentityError::InvalidDocument(format!("Assertion method {} is not defined in the document", method_id))
+        })?;
+        let decoded = Did::decode_key_material(&method.public_key_multibase)?;
+        if decoded.key_type == KeyType::Ed25519 {
+            keys.push(decoded.public_key);
+        }
+    }
+    Ok(keys)
+}
+
+/// Resolves `did` with `resolver` and checks `signature` against the document's
+/// assertion method keys. Succeeds if any of them verifies.
+pub fn verify_with_resolver<R: DidResolver>(resolver: &R, did: &Did, message: &[u8], signature: &[u8]) -> Result<()> {
+    let mut last_error = IdentityError::InvalidDocument(format!("{} has no assertion method keys", did));
+    for public_key in resolve_assertion_keys(resolver, did)? {
+        match crate::verify_with_public_key(&public_key, message, signature) {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+// The primary key of `did`: decoded locally for key-based methods (`did:dverse`,
+// `did:key`), otherwise the first assertion method key of the resolved document.
+fn primary_key<R: DidResolver>(resolver: &R, did: &Did) -> Result<PublicKey> {
+    if did.as_str().starts_with(Did::DID_DVERSE_PREFIX) {
+        return did.to_public_key();
+    }
+    if did.as_str().starts_with(Did::DID_KEY_PREFIX) {
+        return Did::from_did_key(did);
+    }
+    resolve_assertion_keys(resolver, did)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| IdentityError::InvalidDocument(format!("{} has no assertion method keys", did)))
+}
+
+/// Whether `a` and `b` name the same underlying key, even through different DID methods.
+/// Key-based DIDs are decoded locally; anything else (e.g. `did:web`) goes through
+/// `resolver` and is compared by its first assertion method key.
+pub fn keys_equal<R: DidResolver>(a: &Did, b: &Did, resolver: &R) -> Result<bool> {
+    Ok(primary_key(resolver, a)? == primary_key(resolver, b)?)
+}
+
+pub(crate) const PRIMARY_KEY_FRAGMENT: &str = "keys-1";
+const KEY_AGREEMENT_FRAGMENT: &str = "keys-2";
+
+// The document implied by a key-based DID: a single Ed25519 key used for both
+// authentication and assertions, plus the X25519 key agreement key for DIDs built with
+// `Did::from_keypair_with_encryption`.
+pub(crate) fn key_document(did: &Did) -> Result<DidDocument> {
+    let public_key = did.to_public_key()?;
+    let encoded_key = Did::from_public_key(&public_key)?
+        .as_str()
+        .trim_start_matches(Did::DID_DVERSE_PREFIX)
+        .to_string();
+
+    let method_id = format!("{}#{}", did, PRIMARY_KEY_FRAGMENT);
+    let mut builder = DidDocumentBuilder::new(did)
+        .verification_method(VerificationMethod {
+            id: method_id.clone(),
+            method_type: "Ed25519VerificationKey2020".to_string(),
+            controller: did.to_string(),
+            public_key_multibase: encoded_key,
+            extra: Default::default(),
+        })
+        .authentication(method_id.clone())
+        .assertion_method(method_id);
+    if let Ok(key_agreement_key) = did.key_agreement_key() {
+        builder = builder.key_agreement_key(
+            format!("{}#{}", did, KEY_AGREEMENT_FRAGMENT),
+            did,
+            &KeyAgreementPublicKey::from_bytes(key_agreement_key),
+        );
+    }
+    Ok(builder.build())
+}
@@ -0,0 +1,185 @@
+// Backing storage for private key bytes.
+//
+// With the `secure-mem` feature the buffer's pages are locked into RAM (`mlock` on Unix,
+// `VirtualLock` on Windows) so the key is never written to swap, and unlocked on drop.
+// Locking is best effort: if the platform is unsupported or the process is over its
+// locked-memory limit, the bytes are simply kept unlocked.
+//
+// Locks apply to whole pages and do not nest, so every locked page is counted in a
+// process-wide registry and only unlocked when the last key on it is dropped. Dropping a
+// short-lived copy therefore never unlocks a long-lived key sharing its page. Buffers are
+// wiped before their pages are released.
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+
+pub(crate) struct SecretBytes {
+    bytes: Vec<u8>,
+    locked: bool,
+}
+
+impl SecretBytes {
+    pub(crate) fn new(bytes: Vec<u8>) -> Self {
+        let locked = lock(&bytes);
+        SecretBytes { bytes, locked }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        // Wipe while the pages are still locked, so the key never reaches swap.
+        self.bytes.as_mut_slice().zeroize();
+        if self.locked {
+            unlock(&self.bytes);
+        }
+    }
+}
+
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        SecretBytes::new(self.bytes.clone())
+    }
+}
+
+impl core::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("..")
+    }
+}
+
+// Same wire format as the plain `Vec<u8>` this replaced.
+impl Serialize for SecretBytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        self.bytes.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretBytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(SecretBytes::new)
+    }
+}
+
+#[cfg(all(feature = "secure-mem", any(unix, windows)))]
+mod pages {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    // Page start address -> number of live buffers touching that page.
+    static LOCKED_PAGES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+    #[cfg(unix)]
+    fn page_size() -> usize {
+        // SAFETY: sysconf has no preconditions.
+        match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+            size if size > 0 => size as usize,
+            _ => 4096,
+        }
+    }
+
+    // VirtualLock works on 4 KiB pages on every architecture Windows supports.
+    #[cfg(windows)]
+    fn page_size() -> usize {
+        4096
+    }
+
+    #[cfg(unix)]
+    fn lock_page(page: usize, size: usize) -> bool {
+        // SAFETY: `page` is the start of a mapped page holding a live allocation.
+        unsafe { libc::mlock(page as *const libc::c_void, size) == 0 }
+    }
+
+    #[cfg(unix)]
+    fn unlock_page(page: usize, size: usize) {
+        // SAFETY: as for `lock_page`; failure only means the page was not locked.
+        unsafe {
+            libc::munlock(page as *const libc::c_void, size);
+        }
+    }
+
+    #[cfg(windows)]
+    fn lock_page(page: usize, size: usize) -> bool {
+        // SAFETY: `page` is the start of a mapped page holding a live allocation.
+        unsafe {
+            windows_sys::Win32::System::Memory::VirtualLock(page as *const core::ffi::c_void, size) != 0
+        }
+    }
+
+    #[cfg(windows)]
+    fn unlock_page(page: usize, size: usize) {
+        // SAFETY: as for `lock_page`; failure only means the page was not locked.
+        unsafe {
+            windows_sys::Win32::System::Memory::VirtualUnlock(page as *const core::ffi::c_void, size);
+        }
+    }
+
+    fn pages_of(bytes: &[u8]) -> impl Iterator<Item = usize> {
+        let size = page_size();
+        let start = bytes.as_ptr() as usize & !(size - 1);
+        let end = bytes.as_ptr() as usize + bytes.len();
+        (start..end).step_by(size)
+    }
+
+    fn release(registry: &mut BTreeMap<usize, usize>, page: usize) {
+        if let Some(count) = registry.get_mut(&page) {
+            *count -= 1;
+            if *count == 0 {
+                registry.remove(&page);
+                unlock_page(page, page_size());
+            }
+        }
+    }
+
+    pub(super) fn lock(bytes: &[u8]) -> bool {
+        if bytes.is_empty() {
+            return false;
+        }
+        let mut registry = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+        let mut counted = Vec::new();
+        for page in pages_of(bytes) {
+            let count = registry.get(&page).copied().unwrap_or(0);
+            if count == 0 && !lock_page(page, page_size()) {
+                for page in counted {
+                    release(&mut registry, page);
+                }
+                return false;
+            }
+            registry.insert(page, count + 1);
+            counted.push(page);
+        }
+        true
+    }
+
+    pub(super) fn unlock(bytes: &[u8]) {
+        let mut registry = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+        for page in pages_of(bytes) {
+            release(&mut registry, page);
+        }
+    }
+}
+
+#[cfg(all(feature = "secure-mem", any(unix, windows)))]
+use pages::{lock, unlock};
+
+#[cfg(not(all(feature = "secure-mem", any(unix, windows))))]
+fn lock(_bytes: &[u8]) -> bool {
+    false
+}
+
+#[cfg(not(all(feature = "secure-mem", any(unix, windows))))]
+fn unlock(_bytes: &[u8]) {}
@@ -0,0 +1,74 @@
+// Signing abstractions: the `HardwareSigner` trait for keys that never leave a device,
+// and a wrapper for services that sign caller-supplied messages.
+
+use crate::{IdentityError, KeyPair, PublicKey, Result};
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+
+/// An Ed25519 signer whose private key may live outside process memory, e.g. on a
+/// YubiKey or TPM. High-level helpers such as credential issuance, capability delegation
+/// and domain-separated signing accept any implementation; `KeyPair` is the software one.
+pub trait HardwareSigner {
+    fn public_key(&self) -> Result<PublicKey>;
+
+    /// A 64-byte Ed25519 signature over `message`.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+impl HardwareSigner for KeyPair {
+    fn public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        KeyPair::sign(self, message)
+    }
+}
+
+/// Wraps a `KeyPair` with an optional limit on the size of messages signed in one call.
+/// Oversized messages are rejected with `IdentityError::MessageTooLarge`; callers with
+/// large payloads should use `sign_chunks`, which prehashes and is not limited.
+#[derive(Debug, Clone)]
+pub struct GuardedSigner {
+    keypair: KeyPair,
+    max_message_len: Option<usize>,
+}
+
+impl GuardedSigner {
+    /// A signer with no size limit.
+    pub fn new(keypair: KeyPair) -> Self {
+        GuardedSigner { keypair, max_message_len: None }
+    }
+
+    pub fn with_max_message_len(mut self, max_message_len: usize) -> Self {
+        self.max_message_len = Some(max_message_len);
+        self
+    }
+
+    pub fn max_message_len(&self) -> Option<usize> {
+        self.max_message_len
+    }
+
+    pub fn keypair(&self) -> &KeyPair {
+        &self.keypair
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        if let Some(max) = self.max_message_len {
+            if message.len() > max {
+                return Err(IdentityError::MessageTooLarge(format!(
+                    "Message is {} bytes, limit is {}; use sign_chunks for large payloads",
+                    message.len(),
+                    max
+                )));
+            }
+        }
+        self.keypair.sign(message)
+    }
+
+    /// Streaming (Ed25519ph) signing. Not subject to `max_message_len`.
+    pub fn sign_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(&self, chunks: I) -> Result<Vec<u8>> {
+        self.keypair.sign_chunks(chunks)
+    }
+}
@@ -0,0 +1,135 @@
+// X25519 key agreement derived from Ed25519 identity keys.
+//
+// Derivation (the same one libsodium's `crypto_sign_ed25519_sk_to_curve25519` uses):
+//
+// 1. Expand the 32-byte Ed25519 seed with SHA-512 and keep the first 32 bytes. These are
+//    the bytes Ed25519 itself uses as its secret scalar.
+// 2. Clamp them as X25519 requires: clear the low 3 bits of byte 0, clear the top bit of
+//    byte 31 and set bit 254. The clamped value is the X25519 secret.
+// 3. The X25519 public key is that scalar times the Curve25519 base point. This is the
+//    Montgomery form of the Ed25519 public key, i.e. the key `Did::key_agreement_key`
+//    advertises.
+//
+// Reusing one key for signing and key agreement is safe for Ed25519/X25519 in practice, but
+// the shared secret should still go through a KDF (e.g. HKDF) before use as a cipher key.
+
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use multibase::Base;
+use zeroize::Zeroizing;
+
+use crate::{multicodec, IdentityError, KeyPair, Result};
+
+/// A clamped X25519 secret scalar. Wiped from memory when dropped.
+#[derive(Clone)]
+pub struct X25519Secret(Zeroizing<[u8; 32]>);
+
+/// An X25519 public key (a Montgomery u-coordinate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct X25519Public([u8; 32]);
+
+/// The key a DID Document advertises under `keyAgreement`. It holds the same bytes as the
+/// `X25519Public` that `KeyPair::to_x25519_keypair` derives; convert between the two with
+/// `From`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyAgreementPublicKey(X25519Public);
+
+fn clamp(mut bytes: [u8; 32]) -> [u8; 32] {
+    bytes[0] &= 248;
+    bytes[31] &= 127;
+    bytes[31] |= 64;
+    bytes
+}
+
+impl X25519Secret {
+    pub fn public_key(&self) -> X25519Public {
+        X25519Public(MontgomeryPoint::mul_base_clamped(*self.0).to_bytes())
+    }
+
+    /// Computes the shared secret with `peer_public`. Fails if the peer key has small
+    /// order, which would make the result all zeroes regardless of our secret.
+    pub fn diffie_hellman(&self, peer_public: &X25519Public) -> Result<[u8; 32]> {
+        let shared = MontgomeryPoint(peer_public.0).mul_clamped(*self.0).to_bytes();
+        if shared == [0u8; 32] {
+            return Err(IdentityError::InvalidKey("Peer X25519 key has small order".to_string()));
+        }
+        Ok(shared)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for X25519Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("X25519Secret(..)")
+    }
+}
+
+impl X25519Public {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        X25519Public(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl KeyAgreementPublicKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        KeyAgreementPublicKey(X25519Public(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        self.0.as_bytes()
+    }
+
+    /// Base58Btc multibase of the X25519 multicodec prefix and key, as used for
+    /// `publicKeyMultibase` in DID Documents.
+    pub fn to_multibase(&self) -> String {
+        let mut prefixed = multicodec::X25519_PUB.to_vec();
+        prefixed.extend_from_slice(self.as_bytes());
+        multibase::encode(Base::Base58Btc, prefixed)
+    }
+
+    /// Parses a `publicKeyMultibase` value written by `to_multibase`. Other multibases are
+    /// `UnsupportedMultibase`.
+    pub fn from_multibase(encoded: &str) -> Result<Self> {
+        let (base, decoded) = crate::decode_multibase(encoded)?;
+        if base != Base::Base58Btc {
+            return Err(IdentityError::UnsupportedMultibase(format!("Unsupported multibase: {:?}", base)));
+        }
+        let key = decoded.strip_prefix(multicodec::X25519_PUB).ok_or_else(|| {
+            let prefix = &decoded[..decoded.len().min(multicodec::X25519_PUB.len())];
+            IdentityError::UnsupportedMulticodec(format!("Expected the X25519 multicodec prefix, got {:?}", prefix))
+        })?;
+        let bytes: [u8; 32] = key
+            .try_into()
+            .map_err(|_| IdentityError::InvalidKey(format!("X25519 public key must be 32 bytes, got {}", key.len())))?;
+        Ok(KeyAgreementPublicKey::from_bytes(bytes))
+    }
+}
+
+impl From<X25519Public> for KeyAgreementPublicKey {
+    fn from(key: X25519Public) -> Self {
+        KeyAgreementPublicKey(key)
+    }
+}
+
+impl From<KeyAgreementPublicKey> for X25519Public {
+    fn from(key: KeyAgreementPublicKey) -> Self {
+        key.0
+    }
+}
+
+impl KeyPair {
+    /// Converts this signing key pair into an X25519 key pair for Diffie-Hellman, as
+    /// described in the `x25519` module docs.
+    pub fn to_x25519_keypair(&self) -> Result<(X25519Secret, X25519Public)> {
+        let signing_key = crate::signing_key_from(&self.private_key)?;
+        let secret = X25519Secret(Zeroizing::new(clamp(signing_key.to_scalar_bytes())));
+        let public = secret.public_key();
+        Ok((secret, public))
+    }
+}
@@ -0,0 +1,47 @@
+use dverse_identity::{Did, IdentityAgent, IdentityError, KeyPair, PrivateKey};
+
+#[test]
+fn test_agent_signs_for_loaded_identity() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let private_key_debug = format!("{:?}", keypair.private_key.as_bytes());
+    let public_key = keypair.public_key.clone();
+
+    let mut agent = IdentityAgent::new();
+    let did = agent.add_identity(keypair).expect("Should load identity");
+    assert_eq!(agent.list_identities(), vec![did.clone()]);
+
+    let message = b"Signed through the agent";
+    let signature = agent.sign(&did, message).expect("Agent should sign");
+
+    let verifier = KeyPair {
+        private_key: PrivateKey::from_bytes(vec![0; 32]), // Dummy private key, not used for verification
+        public_key,
+    };
+    verifier.verify(message, &signature).expect("Agent signature should verify");
+
+    // The only views the agent offers are DIDs and signatures.
+    assert!(!format!("{:?}", agent).contains(&private_key_debug));
+
+    let unknown = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+    assert!(matches!(agent.sign(&unknown, message), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_agent_lock_and_unlock() {
+    let mut agent = IdentityAgent::new();
+    let did = agent.add_identity(KeyPair::generate().unwrap()).unwrap();
+
+    agent.lock("correct horse").expect("Should lock");
+    assert!(agent.is_locked());
+    assert!(matches!(agent.sign(&did, b"msg"), Err(IdentityError::AgentLocked(_))));
+    assert!(matches!(agent.add_identity(KeyPair::generate().unwrap()), Err(IdentityError::AgentLocked(_))));
+
+    assert!(matches!(agent.unlock("wrong"), Err(IdentityError::AgentLocked(_))));
+    assert!(agent.is_locked());
+
+    agent.unlock("correct horse").expect("Should unlock");
+    agent.sign(&did, b"msg").expect("Unlocked agent should sign");
+
+    assert!(agent.remove_identity(&did).unwrap());
+    assert!(agent.list_identities().is_empty());
+}
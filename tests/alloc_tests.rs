@@ -0,0 +1,46 @@
+// Runs in its own test binary because it installs a counting global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use dverse_identity::KeyPair;
+
+struct CountingAllocator;
+
+thread_local! {
+    // Per-thread so allocations made by the test harness on other threads are not counted.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+#[test]
+fn test_verify_with_borrowed_key_does_not_allocate() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"allocation-free verification";
+    let signature = keypair.sign(message).expect("Should sign");
+    let public_key = &keypair.public_key;
+
+    let before = allocations();
+    let result = dverse_identity::verify(public_key, message, &signature);
+    let after = allocations();
+
+    result.expect("Signature should verify");
+    assert_eq!(after, before, "verify should not allocate");
+}
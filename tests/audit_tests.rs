@@ -0,0 +1,49 @@
+use dverse_identity::{AuditLog, IdentityError, KeyPair};
+
+fn sample_log(keypair: &KeyPair) -> AuditLog {
+    let mut log = AuditLog::new();
+    for entry in ["user created", "role granted: admin", "password rotated", "user disabled"] {
+        log.append(keypair, entry.as_bytes()).expect("Should append entry");
+    }
+    log
+}
+
+#[test]
+fn test_audit_log_intact_chain_verifies() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let log = sample_log(&keypair);
+
+    assert_eq!(log.len(), 4);
+    assert_eq!(log.records()[0].prev_hash, [0u8; 32]);
+    assert_eq!(log.records()[2].prev_hash, log.records()[1].hash());
+    log.verify_chain(&keypair.public_key).expect("Intact chain should verify");
+
+    let other = KeyPair::generate().expect("Should generate keypair");
+    assert!(log.verify_chain(&other.public_key).is_err());
+}
+
+#[test]
+fn test_audit_log_mutated_entry_fails() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let mut records = sample_log(&keypair).records().to_vec();
+    records[1].entry = b"role granted: viewer".to_vec();
+
+    let result = AuditLog::from_records(records).verify_chain(&keypair.public_key);
+    assert!(matches!(result, Err(IdentityError::DalekError(_))));
+}
+
+#[test]
+fn test_audit_log_reordered_or_removed_entries_fail() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let records = sample_log(&keypair).records().to_vec();
+
+    let mut reordered = records.clone();
+    reordered.swap(1, 2);
+    let result = AuditLog::from_records(reordered).verify_chain(&keypair.public_key);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+
+    let mut truncated_front = records;
+    truncated_front.remove(0);
+    let result = AuditLog::from_records(truncated_front).verify_chain(&keypair.public_key);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+}
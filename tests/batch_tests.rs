@@ -0,0 +1,57 @@
+use dverse_identity::{verify_batch, verify_batch_indexed, IdentityError, KeyPair, PublicKey};
+
+fn build_items(count: usize) -> Vec<(PublicKey, Vec<u8>, Vec<u8>)> {
+    (0..count)
+        .map(|i| {
+            let keypair = KeyPair::generate().expect("Should generate keypair");
+            let message = format!("D-Verse event #{}", i).into_bytes();
+            let signature = keypair.sign(&message).expect("Should sign message");
+            (keypair.public_key, message, signature)
+        })
+        .collect()
+}
+
+#[test]
+fn test_verify_batch_accepts_valid_batch() {
+    let items = build_items(100);
+    verify_batch(&items).expect("Valid batch should verify");
+    assert!(verify_batch_indexed(&items).is_empty());
+    verify_batch(&[]).expect("Empty batch should verify");
+}
+
+#[test]
+fn test_verify_batch_reports_failures() {
+    let mut items = build_items(10);
+    items[3].1 = b"tampered".to_vec();
+    items[7].2[0] ^= 1;
+
+    assert!(matches!(verify_batch(&items), Err(IdentityError::SignatureError(_))));
+    assert_eq!(verify_batch_indexed(&items), vec![3, 7]);
+
+    items[5].2.truncate(10);
+    assert!(matches!(verify_batch(&items), Err(IdentityError::InvalidSignatureLength(_))));
+    assert_eq!(verify_batch_indexed(&items), vec![3, 5, 7]);
+}
+
+#[test]
+fn test_verify_batch_of_100_against_individual() {
+    // Timing lives in `benches/verify_batch.rs`; this only checks the two paths agree.
+    let individual_failures = |items: &[(PublicKey, Vec<u8>, Vec<u8>)]| -> Vec<usize> {
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, (public_key, message, signature))| dverse_identity::verify(public_key, message, signature).is_err())
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    let mut items = build_items(100);
+    assert!(individual_failures(&items).is_empty());
+    assert!(verify_batch(&items).is_ok());
+    assert!(verify_batch_indexed(&items).is_empty());
+
+    items[42].2[10] ^= 1;
+    assert_eq!(individual_failures(&items), vec![42]);
+    assert!(verify_batch(&items).is_err());
+    assert_eq!(verify_batch_indexed(&items), vec![42]);
+}
@@ -0,0 +1,87 @@
+use dverse_identity::blind::{self, BlindCommitment, BlindSignature, BlindedMessage, BlindingFactor};
+use dverse_identity::{IdentityError, KeyPair};
+
+fn issue(issuer: &KeyPair, message: &[u8]) -> (Vec<u8>, [u8; 32], [u8; 32], [u8; 32]) {
+    let (nonce, commitment) = issuer.blind_commit();
+    let factor = BlindingFactor::random();
+    let (blinded, unblinder) =
+        blind::blind(message, &issuer.public_key, &commitment, &factor).expect("Blinding should succeed");
+    let blind_signature = issuer.sign_blinded(nonce, &blinded).expect("Blind signing should succeed");
+    let signature = blind::unblind(&blind_signature, &unblinder).expect("Unblinding should succeed");
+    (signature.to_vec(), commitment.to_bytes(), blinded.to_bytes(), blind_signature.to_bytes())
+}
+
+#[test]
+fn test_unblinded_signature_verifies_with_normal_verify() {
+    let issuer = KeyPair::generate().expect("Should generate issuer");
+    let message = b"credential: over 18";
+
+    let (signature, _, _, _) = issue(&issuer, message);
+
+    issuer.verify(message, &signature).expect("Unblinded signature should verify");
+    assert!(issuer.verify(b"credential: over 21", &signature).is_err());
+}
+
+#[test]
+fn test_issuer_transcript_does_not_appear_in_signature() {
+    let issuer = KeyPair::generate().expect("Should generate issuer");
+    let message = b"same credential";
+
+    let (first, first_commitment, first_challenge, first_response) = issue(&issuer, message);
+    let (second, _, _, _) = issue(&issuer, message);
+
+    // The signature's R and s are re-randomized, so nothing the issuer saw shows up in it.
+    assert_ne!(&first[..32], &first_commitment[..]);
+    assert_ne!(&first[32..], &first_response[..]);
+    assert_ne!(&first[32..], &first_challenge[..]);
+    // Two issuances of the same message give unrelated signatures.
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_unblind_rejects_response_from_wrong_key() {
+    let issuer = KeyPair::generate().expect("Should generate issuer");
+    let impostor = KeyPair::generate().expect("Should generate impostor");
+
+    let (nonce, commitment) = impostor.blind_commit();
+    let factor = BlindingFactor::random();
+    let (blinded, unblinder) =
+        blind::blind(b"message", &issuer.public_key, &commitment, &factor).expect("Blinding should succeed");
+    let blind_signature: BlindSignature = impostor.sign_blinded(nonce, &blinded).expect("Blind signing should succeed");
+
+    let result = blind::unblind(&blind_signature, &unblinder);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+}
+
+#[test]
+fn test_blind_messages_round_trip_through_bytes() {
+    // The identity point and the group order L, the smallest non-canonical scalar.
+    const IDENTITY: [u8; 32] = {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes
+    };
+    const GROUP_ORDER: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+    ];
+
+    let issuer = KeyPair::generate().expect("Should generate issuer");
+    let message = b"sent over the wire";
+
+    // Every message crosses the wire as bytes and is rebuilt on the other side.
+    let (nonce, commitment) = issuer.blind_commit();
+    let received = BlindCommitment::from_bytes(&commitment.to_bytes()).expect("Should rebuild commitment");
+    let factor = BlindingFactor::random();
+    let (blinded, unblinder) =
+        blind::blind(message, &issuer.public_key, &received, &factor).expect("Blinding should succeed");
+    let received = BlindedMessage::from_bytes(&blinded.to_bytes()).expect("Should rebuild blinded message");
+    let blind_signature = issuer.sign_blinded(nonce, &received).expect("Blind signing should succeed");
+    let received = BlindSignature::from_bytes(&blind_signature.to_bytes()).expect("Should rebuild blind signature");
+    let signature = blind::unblind(&received, &unblinder).expect("Unblinding should succeed");
+    issuer.verify(message, &signature.to_vec()).expect("Unblinded signature should verify");
+
+    assert!(matches!(BlindCommitment::from_bytes(&IDENTITY), Err(IdentityError::InvalidKey(_))));
+    assert!(matches!(BlindedMessage::from_bytes(&GROUP_ORDER), Err(IdentityError::SignatureError(_))));
+    assert!(matches!(BlindSignature::from_bytes(&GROUP_ORDER), Err(IdentityError::SignatureError(_))));
+}
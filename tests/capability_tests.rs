@@ -0,0 +1,126 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use dverse_identity::capability::{self, Caveat, CapabilityToken};
+use dverse_identity::{Did, IdentityError, KeyPair, MockClock};
+
+fn identity() -> (KeyPair, Did) {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    (keypair, did)
+}
+
+fn in_one_hour() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 3600
+}
+
+#[test]
+fn test_capability_delegation_chain_verifies() {
+    let (root_keys, root_did) = identity();
+    let (alice_keys, alice_did) = identity();
+    let (_, bob_did) = identity();
+
+    let root_token = capability::delegate(
+        &root_keys,
+        &root_did,
+        &alice_did,
+        vec![Caveat::Resource("storage://photos".to_string()), Caveat::ExpiresAt(in_one_hour())],
+    )
+    .expect("Should delegate");
+    capability::verify_capability(&root_token, &root_did, "write", "storage://photos")
+        .expect("Root delegation should verify");
+
+    let bob_token = capability::redelegate(&root_token, &alice_keys, &bob_did, vec![Caveat::Action("read".to_string())])
+        .expect("Should re-delegate");
+    assert_eq!(bob_token.caveats.len(), 3);
+    capability::verify_capability(&bob_token, &root_did, "read", "storage://photos")
+        .expect("Delegation chain should verify");
+
+    // Tokens survive a JSON round-trip intact.
+    let json = serde_json::to_string(&bob_token).expect("Should serialize token");
+    let decoded: CapabilityToken = serde_json::from_str(&json).expect("Should deserialize token");
+    capability::verify_capability(&decoded, &root_did, "read", "storage://photos").expect("Decoded token should verify");
+
+    // The chain is not rooted at Alice.
+    let result = capability::verify_capability(&bob_token, &alice_did, "read", "storage://photos");
+    assert!(matches!(result, Err(IdentityError::InvalidCapability(_))));
+}
+
+#[test]
+fn test_capability_tampered_caveat_fails() {
+    let (root_keys, root_did) = identity();
+    let (_, alice_did) = identity();
+
+    let mut token = capability::delegate(&root_keys, &root_did, &alice_did, vec![Caveat::Action("read".to_string())])
+        .expect("Should delegate");
+    token.caveats[0] = Caveat::Action("write".to_string());
+
+    let result = capability::verify_capability(&token, &root_did, "write", "storage://photos");
+    assert!(matches!(result, Err(IdentityError::DalekError(_))));
+}
+
+#[test]
+fn test_capability_expired_token_fails() {
+    let (root_keys, root_did) = identity();
+    let (_, alice_did) = identity();
+
+    let expired = capability::delegate(&root_keys, &root_did, &alice_did, vec![Caveat::ExpiresAt(1)]).unwrap();
+    let result = capability::verify_capability(&expired, &root_did, "read", "storage://photos");
+    assert!(matches!(result, Err(IdentityError::InvalidCapability(_))));
+}
+
+#[test]
+fn test_capability_expiry_with_mock_clock() {
+    let (root_keys, root_did) = identity();
+    let (_, alice_did) = identity();
+    let expires_at = 1_700_000_000;
+    let token = capability::delegate(&root_keys, &root_did, &alice_did, vec![Caveat::ExpiresAt(expires_at)])
+        .expect("Should delegate");
+
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(expires_at - 1));
+    capability::verify_capability_with_clock(&token, &root_did, "read", "storage://photos", &clock)
+        .expect("Should be valid before expiry");
+
+    clock.advance(Duration::from_secs(1));
+    let result = capability::verify_capability_with_clock(&token, &root_did, "read", "storage://photos", &clock);
+    assert!(matches!(result, Err(IdentityError::InvalidCapability(_))));
+}
+
+#[test]
+fn test_capability_enforces_action_and_resource_caveats() {
+    let (root_keys, root_did) = identity();
+    let (alice_keys, alice_did) = identity();
+    let (_, bob_did) = identity();
+
+    let root_token =
+        capability::delegate(&root_keys, &root_did, &alice_did, vec![Caveat::Resource("storage://photos".to_string())])
+            .expect("Should delegate");
+    let bob_token = capability::redelegate(&root_token, &alice_keys, &bob_did, vec![Caveat::Action("read".to_string())])
+        .expect("Should re-delegate");
+
+    capability::verify_capability(&bob_token, &root_did, "read", "storage://photos").expect("Read should be allowed");
+    for (action, resource) in [("write", "storage://photos"), ("read", "storage://videos")] {
+        let result = capability::verify_capability(&bob_token, &root_did, action, resource);
+        assert!(matches!(result, Err(IdentityError::InvalidCapability(_))), "{} {} should be rejected", action, resource);
+    }
+
+    // A caveat type this crate does not know cannot be smuggled past verification.
+    let mut value = serde_json::to_value(&bob_token).expect("Should serialize token");
+    value["caveats"][0] = serde_json::json!({ "type": "ipRange", "value": "10.0.0.0/8" });
+    assert!(serde_json::from_value::<CapabilityToken>(value).is_err());
+}
+
+#[test]
+fn test_capability_signature_is_domain_separated() {
+    let (root_keys, root_did) = identity();
+    let (_, alice_did) = identity();
+    let mut token = capability::delegate(&root_keys, &root_did, &alice_did, vec![]).expect("Should delegate");
+
+    // A plain signature over the same payload must not pass as a capability signature.
+    let payload = format!(
+        r#"{{"issuer":"{}","audience":"{}","caveats":[],"parent_signature":null}}"#,
+        root_did, alice_did
+    );
+    token.signature = root_keys.sign(payload.as_bytes()).expect("Should sign");
+    let result = capability::verify_capability(&token, &root_did, "read", "storage://photos");
+    assert!(matches!(result, Err(IdentityError::DalekError(_))));
+}
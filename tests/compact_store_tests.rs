@@ -0,0 +1,46 @@
+use multibase::Base;
+
+use dverse_identity::{CompactDidStore, Did, IdentityError, KeyPair};
+
+fn new_did() -> Did {
+    Did::from_public_key(&KeyPair::generate().expect("Should generate keypair").public_key).expect("Should derive DID")
+}
+
+#[test]
+fn test_compact_store_returns_canonical_did() {
+    let did = new_did();
+    let mut store = CompactDidStore::new();
+
+    assert!(store.insert(&did).expect("Should insert DID"));
+    assert!(!store.insert(&did).expect("Should insert DID"), "Second insert is a duplicate");
+    assert_eq!(store.len(), 1);
+
+    let found = store.get(&did).expect("Should look up DID").expect("DID should be stored");
+    assert_eq!(found.as_str(), did.as_str());
+
+    // Another encoding of the same key finds the same entry, returned in canonical form.
+    let base64url = did.reencode(Base::Base64Url).expect("Should re-encode DID");
+    assert!(store.contains(&base64url).expect("Should look up DID"));
+    assert_eq!(store.get(&base64url).expect("Should look up DID"), Some(did.clone()));
+
+    let other = new_did();
+    assert_eq!(store.get(&other).expect("Should look up DID"), None);
+    assert!(store.insert(&other).expect("Should insert DID"));
+
+    let mut stored: Vec<Did> = store.iter().collect();
+    stored.sort();
+    let mut expected = vec![did.clone(), other];
+    expected.sort();
+    assert_eq!(stored, expected);
+
+    assert!(store.remove(&did).expect("Should remove DID"));
+    assert!(!store.remove(&did).expect("Should remove DID"));
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_compact_store_rejects_invalid_dids() {
+    let mut store = CompactDidStore::new();
+    assert!(matches!(store.insert(&Did::from("garbage")), Err(IdentityError::InvalidDidFormat(_))));
+    assert!(store.is_empty());
+}
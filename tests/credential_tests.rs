@@ -0,0 +1,85 @@
+use dverse_identity::credential;
+use dverse_identity::{Did, IdentityError, KeyPair, Profile, ProfileCredential};
+
+fn sample_profile() -> Profile {
+    Profile {
+        display_name: "Ada Lovelace".to_string(),
+        avatar_url: Some("https://example.com/ada.png".to_string()),
+        contact_endpoints: vec!["mailto:ada@example.com".to_string(), "https://ada.example.com".to_string()],
+    }
+}
+
+#[test]
+fn test_profile_credential_round_trip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let credential = ProfileCredential::issue(&keypair, sample_profile()).expect("Should issue credential");
+    credential.verify().expect("Fresh credential should verify");
+
+    let json = credential.to_json().expect("Should serialize credential");
+    assert!(json.contains(r#""credentialSubject":{"id":"did:dverse:"#));
+    assert!(json.contains(r#""displayName":"Ada Lovelace""#));
+    assert!(json.contains(r#""type":"DverseEd25519Signature2024""#));
+
+    let parsed = ProfileCredential::from_json(&json).expect("Should parse credential");
+    assert_eq!(parsed, credential);
+    parsed.verify().expect("Parsed credential should verify");
+    assert_eq!(parsed.profile(), &sample_profile());
+    assert_eq!(parsed.issuer, Did::from_public_key(&keypair.public_key).expect("Should derive DID"));
+}
+
+#[test]
+fn test_profile_credential_edit_after_signing_fails() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let credential = ProfileCredential::issue(&keypair, sample_profile()).expect("Should issue credential");
+
+    let mut renamed = credential.clone();
+    renamed.credential_subject.profile.display_name = "Mallory".to_string();
+    assert!(renamed.verify().is_err());
+
+    let mut extra_contact = credential.clone();
+    extra_contact.credential_subject.profile.contact_endpoints.push("mailto:mallory@example.com".to_string());
+    assert!(extra_contact.verify().is_err());
+
+    let other = KeyPair::generate().expect("Should generate keypair");
+    let mut reissued = credential;
+    reissued.issuer = Did::from_public_key(&other.public_key).expect("Should derive DID");
+    assert!(matches!(reissued.verify(), Err(IdentityError::InvalidCredential(_))));
+}
+
+#[test]
+fn test_verify_jsonl_reports_each_line() {
+    let alice = KeyPair::generate().expect("Should generate keypair");
+    let bob = KeyPair::generate().expect("Should generate keypair");
+    let valid = ProfileCredential::issue(&alice, sample_profile()).expect("Should issue credential");
+    let mut tampered = ProfileCredential::issue(&bob, sample_profile()).expect("Should issue credential");
+    tampered.credential_subject.profile.display_name = "Mallory".to_string();
+
+    let jsonl = format!(
+        "{}\n\n{}\nnot json\n",
+        valid.to_json().expect("Should serialize"),
+        tampered.to_json().expect("Should serialize")
+    );
+    let results: Vec<_> = credential::verify_jsonl(jsonl.as_bytes()).collect();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().expect("First line should verify"), &valid.issuer);
+    assert!(results[1].is_err());
+    assert!(matches!(results[2], Err(IdentityError::DecodingError(_))));
+}
+
+#[test]
+fn test_verify_jsonl_rejects_oversized_lines() {
+    let alice = KeyPair::generate().expect("Should generate keypair");
+    let valid = ProfileCredential::issue(&alice, sample_profile()).expect("Should issue credential");
+    let json = valid.to_json().expect("Should serialize");
+
+    // An oversized line is reported once and skipped, and the stream carries on after it.
+    let oversized = "x".repeat(credential::MAX_JSONL_LINE_BYTES * 3);
+    let jsonl = format!("{}\r\n{}\n{}", json, oversized, json);
+    let results: Vec<_> = credential::verify_jsonl(jsonl.as_bytes()).collect();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().expect("First line should verify"), &valid.issuer);
+    assert!(matches!(results[1], Err(IdentityError::DecodingError(_))));
+    assert_eq!(results[2].as_ref().expect("Line after oversized one should verify"), &valid.issuer);
+}
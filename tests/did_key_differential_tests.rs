@@ -0,0 +1,64 @@
+// Differential test of the did:key conversion. The fixture was computed outside this
+// crate (OpenSSL's Ed25519 and an independent base58btc encoder) for random seeds, plus
+// the all-zero seed from the did:key test vectors. Any mismatch means our did:key strings
+// have diverged from the rest of the ecosystem.
+
+use dverse_identity::{Did, KeyPair};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct DidKeyVector {
+    source: String,
+    seed: String,
+    public_key: String,
+    did_key: String,
+}
+
+fn load_vectors() -> Vec<DidKeyVector> {
+    serde_json::from_str(include_str!("fixtures/did_key_vectors.json")).expect("Fixture should parse")
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("Fixture should contain valid hex"))
+        .collect()
+}
+
+fn keypair_for(vector: &DidKeyVector) -> KeyPair {
+    let seed: [u8; 32] = decode_hex(&vector.seed).try_into().expect("Fixture seed should be 32 bytes");
+    KeyPair::from_seed(&seed).expect("Should build keypair from seed")
+}
+
+#[test]
+fn test_to_did_key_matches_reference_implementation() {
+    let vectors = load_vectors();
+    assert!(vectors.len() >= 12);
+
+    for vector in &vectors {
+        let keypair = keypair_for(vector);
+        assert_eq!(
+            keypair.public_key.as_bytes(),
+            decode_hex(&vector.public_key).as_slice(),
+            "Public key derivation differs for {}",
+            vector.source
+        );
+
+        let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+        let did_key = did.to_did_key().expect("Should convert to did:key");
+        assert_eq!(did_key.as_str(), vector.did_key, "did:key encoding differs for {}", vector.source);
+    }
+}
+
+#[test]
+fn test_from_did_key_matches_reference_implementation() {
+    for vector in load_vectors() {
+        let public_key = Did::from_did_key(&Did::from(vector.did_key.as_str())).expect("Should decode reference did:key");
+        assert_eq!(
+            public_key.as_bytes(),
+            decode_hex(&vector.public_key).as_slice(),
+            "did:key decoding differs for {}",
+            vector.source
+        );
+    }
+}
@@ -0,0 +1,45 @@
+// Pins the did:dverse encoding (multicodec prefix, multibase, method prefix) to known
+// vectors. If any of these fail, the DID format has changed and existing identities
+// would no longer resolve.
+
+use dverse_identity::{Did, PublicKey};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct DidVector {
+    source: String,
+    public_key: String,
+    did: String,
+}
+
+fn load_vectors() -> Vec<DidVector> {
+    serde_json::from_str(include_str!("fixtures/did_vectors.json")).expect("Fixture should parse")
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("Fixture should contain valid hex"))
+        .collect()
+}
+
+#[test]
+fn test_did_vectors_encode_exactly() {
+    let vectors = load_vectors();
+    assert!(vectors.len() >= 5);
+
+    for vector in &vectors {
+        let public_key = PublicKey::from_bytes(decode_hex(&vector.public_key));
+        let did = Did::from_public_key(&public_key).expect("Should derive DID");
+        assert_eq!(did.as_str(), vector.did, "DID encoding changed for {}", vector.source);
+    }
+}
+
+#[test]
+fn test_did_vectors_decode_exactly() {
+    for vector in load_vectors() {
+        let did = Did::from(vector.did.as_str());
+        let public_key = did.to_public_key().expect("Should decode vector DID");
+        assert_eq!(public_key.as_bytes(), decode_hex(&vector.public_key).as_slice(), "Decoding changed for {}", vector.source);
+    }
+}
@@ -0,0 +1,415 @@
+use dverse_identity::{
+    Did,
+    DidDocument,
+    DidDocumentBuilder,
+    DocumentPatch,
+    IdentityError,
+    KeyAgreementPublicKey,
+    KeyPair,
+    KeyUsage,
+    Service,
+    VerificationMethod,
+    X25519Public,
+};
+
+fn key_method(did: &Did, fragment: &str) -> VerificationMethod {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let key_did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    VerificationMethod {
+        id: format!("{}#{}", did, fragment),
+        method_type: "Ed25519VerificationKey2020".to_string(),
+        controller: did.to_string(),
+        public_key_multibase: key_did.as_str().trim_start_matches("did:dverse:").to_string(),
+        extra: Default::default(),
+    }
+}
+
+fn service(did: &Did, fragment: &str, endpoint: &str) -> Service {
+    Service {
+        id: format!("{}#{}", did, fragment),
+        service_type: "LinkedDomains".to_string(),
+        service_endpoint: endpoint.to_string(),
+        extra: Default::default(),
+    }
+}
+
+fn base_document() -> (Did, DidDocument, VerificationMethod) {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let primary = key_method(&did, "keys-1");
+    let document = DidDocumentBuilder::new(&did)
+        .verification_method(primary.clone())
+        .authentication(primary.id.clone())
+        .assertion_method(primary.id.clone())
+        .service(service(&did, "home", "https://old.example.com"))
+        .build();
+    (did, document, primary)
+}
+
+#[test]
+fn test_document_diff_and_apply_reproduces_target() {
+    let (did, source, primary) = base_document();
+    let secondary = key_method(&did, "keys-2");
+    let target = DidDocumentBuilder::new(&did)
+        .verification_method(primary.clone())
+        .verification_method(secondary.clone())
+        .authentication(primary.id.clone())
+        .authentication(secondary.id.clone())
+        .assertion_method(primary.id.clone())
+        .service(service(&did, "home", "https://new.example.com"))
+        .service(service(&did, "inbox", "https://inbox.example.com"))
+        .build();
+
+    let patch = source.diff(&target);
+    assert_eq!(patch.added_verification_methods, vec![secondary]);
+    assert!(patch.removed_verification_methods.is_empty());
+    assert!(patch.removed_services.is_empty());
+    assert_eq!(patch.replaced_services, vec![service(&did, "home", "https://new.example.com")]);
+    assert!(patch.order.is_none());
+
+    let patched = source.apply_patch(&patch).expect("Patch should apply");
+    assert_eq!(patched, target);
+    assert!(patched.diff(&target).is_empty());
+}
+
+#[test]
+fn test_document_patch_carries_context_and_unmodelled_members() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let source = did.to_did_document().expect("Should build document");
+
+    let mut target = source.clone();
+    target.context.push("https://w3id.org/security/suites/ed25519-2020/v1".to_string());
+    target.extra.insert("controller".to_string(), serde_json::json!(did.to_string()));
+    let target = target.with_self_proof(&keypair, &format!("{}#keys-1", did)).expect("Should sign document");
+
+    let patch = source.diff(&target);
+    assert_eq!(patch.context.as_ref(), Some(&target.context));
+    assert_eq!(patch.extra.as_ref(), Some(&target.extra));
+    assert!(patch.order.is_none());
+
+    // Everything but the proof is reproduced; the patched document has to be signed again.
+    let patched = source.apply_patch(&patch).expect("Patch should apply");
+    assert_eq!(patched, DidDocument { proof: None, ..target.clone() });
+    assert!(matches!(patched.verify_self_proof(), Err(IdentityError::InvalidDocument(_))));
+    assert!(patched.diff(&target).is_empty());
+
+    let json = serde_json::to_string(&patch).expect("Should serialize patch");
+    let parsed: DocumentPatch = serde_json::from_str(&json).expect("Should parse patch");
+    assert_eq!(parsed, patch);
+}
+
+#[test]
+fn test_document_patch_replaces_entries_in_place() {
+    let (did, _, primary) = base_document();
+    let secondary = key_method(&did, "keys-2");
+    let source = DidDocumentBuilder::new(&did)
+        .verification_method(primary.clone())
+        .verification_method(secondary.clone())
+        .build();
+    let rotated = VerificationMethod { id: primary.id.clone(), ..key_method(&did, "keys-1") };
+    let target = DidDocumentBuilder::new(&did)
+        .verification_method(rotated.clone())
+        .verification_method(secondary)
+        .build();
+
+    let patch = source.diff(&target);
+    assert_eq!(patch.replaced_verification_methods, vec![rotated]);
+    assert!(patch.added_verification_methods.is_empty());
+    assert!(patch.removed_verification_methods.is_empty());
+
+    let patched = source.apply_patch(&patch).expect("Patch should apply");
+    assert_eq!(patched, target);
+    assert_eq!(
+        patched.to_json().expect("Should serialize document"),
+        target.to_json().expect("Should serialize document")
+    );
+}
+
+#[test]
+fn test_document_patch_reproduces_reordering() {
+    let (did, _, primary) = base_document();
+    let secondary = key_method(&did, "keys-2");
+    let source = DidDocumentBuilder::new(&did)
+        .verification_method(primary.clone())
+        .verification_method(secondary.clone())
+        .service(service(&did, "home", "https://example.com"))
+        .service(service(&did, "inbox", "https://inbox.example.com"))
+        .build();
+    let target = DidDocumentBuilder::new(&did)
+        .verification_method(secondary)
+        .verification_method(primary)
+        .service(service(&did, "inbox", "https://inbox.example.com"))
+        .service(service(&did, "home", "https://example.com"))
+        .build();
+
+    let patch = source.diff(&target);
+    assert!(!patch.is_empty());
+    assert!(patch.order.is_some());
+
+    let patched = source.apply_patch(&patch).expect("Patch should apply");
+    assert_eq!(patched, target);
+    assert_eq!(
+        patched.to_json().expect("Should serialize document"),
+        target.to_json().expect("Should serialize document")
+    );
+
+    let mut bad_order = patch.clone();
+    bad_order.order.as_mut().expect("Patch should record an order").service.pop();
+    assert!(matches!(source.apply_patch(&bad_order), Err(IdentityError::InvalidDocument(_))));
+}
+
+#[test]
+fn test_document_patch_removes_entries() {
+    let (did, source, primary) = base_document();
+    let target = DidDocumentBuilder::new(&did).verification_method(primary).build();
+
+    let patched = source.apply_patch(&source.diff(&target)).expect("Patch should apply");
+    assert_eq!(patched, target);
+}
+
+#[test]
+fn test_document_patch_rejects_mismatched_document() {
+    let (_, source, _) = base_document();
+    let (_, other, _) = base_document();
+
+    let patch = source.diff(&source);
+    let result = other.apply_patch(&patch);
+    assert!(matches!(result, Err(IdentityError::InvalidDocument(_))));
+}
+
+#[test]
+fn test_document_serialization_is_byte_identical() {
+    let (did, _, primary) = base_document();
+    let build = || {
+        DidDocumentBuilder::new(&did)
+            .verification_method(primary.clone())
+            .authentication(primary.id.clone())
+            .assertion_method(primary.id.clone())
+            .service(service(&did, "home", "https://example.com"))
+            .build()
+    };
+
+    let first = build().to_json().expect("Should serialize document");
+    let second = build().to_json().expect("Should serialize document");
+    assert_eq!(first, second);
+
+    let expected_prefix = format!(r#"{{"@context":["https://www.w3.org/ns/did/v1"],"id":"{}","verificationMethod":"#, did);
+    assert!(first.starts_with(&expected_prefix), "Unexpected field order: {}", first);
+    let auth = first.find(r#""authentication""#).unwrap();
+    let assertion = first.find(r#""assertionMethod""#).unwrap();
+    let services = first.find(r#""service""#).unwrap();
+    assert!(auth < assertion && assertion < services);
+
+    let parsed = DidDocument::from_json(&first).expect("Should parse document");
+    assert_eq!(parsed.to_json().unwrap(), first);
+}
+
+#[test]
+fn test_document_pretty_output_round_trips() {
+    let (_, document, _) = base_document();
+
+    let pretty = document.to_string_pretty();
+    assert!(pretty.contains('\n'));
+    assert_eq!(document.to_string(), pretty);
+    assert_ne!(pretty, document.to_json().expect("Should serialize document"));
+
+    let parsed = DidDocument::from_json(&pretty).expect("Should parse pretty document");
+    assert_eq!(parsed, document);
+}
+
+#[test]
+fn test_document_also_known_as_round_trips() {
+    let (did, _, primary) = base_document();
+    let aliases = vec!["did:web:alice.example.com".to_string(), "https://alice.example.com".to_string()];
+    let document = DidDocumentBuilder::new(&did)
+        .also_known_as(aliases.clone())
+        .verification_method(primary)
+        .build();
+
+    let json = document.to_json().expect("Should serialize document");
+    let expected_prefix = format!(
+        r#"{{"@context":["https://www.w3.org/ns/did/v1"],"id":"{}","alsoKnownAs":["did:web:alice.example.com","https://alice.example.com"],"#,
+        did
+    );
+    assert!(json.starts_with(&expected_prefix), "Unexpected output: {}", json);
+
+    let parsed = DidDocument::from_json(&json).expect("Should parse document");
+    assert_eq!(parsed.also_known_as, aliases);
+    assert_eq!(parsed, document);
+
+    let without_aliases = DidDocumentBuilder::new(&did).build();
+    let patch = without_aliases.diff(&document);
+    assert_eq!(patch.added_also_known_as, aliases);
+    assert_eq!(without_aliases.apply_patch(&patch).expect("Patch should apply").also_known_as, aliases);
+}
+
+#[test]
+fn test_authentication_only_key_rejected_for_assertions() {
+    let controller = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&controller.public_key).expect("Should derive DID");
+    let login_key = KeyPair::generate().expect("Should generate keypair");
+    let login_method = VerificationMethod {
+        id: format!("{}#login", did),
+        method_type: "Ed25519VerificationKey2020".to_string(),
+        controller: did.to_string(),
+        public_key_multibase: Did::from_public_key(&login_key.public_key)
+            .expect("Should derive DID")
+            .as_str()
+            .trim_start_matches("did:dverse:")
+            .to_string(),
+        extra: Default::default(),
+    };
+    let document = DidDocumentBuilder::new(&did)
+        .verification_method(login_method.clone())
+        .authentication(login_method.id)
+        .build();
+
+    assert_eq!(document.key_usages(&login_key.public_key), vec![KeyUsage::Authentication]);
+    assert!(document.key_usages(&controller.public_key).is_empty());
+
+    let signature = login_key.sign(b"challenge").expect("Should sign");
+    document
+        .verify_with_usage(&login_key.public_key, b"challenge", &signature, KeyUsage::Authentication)
+        .expect("Authentication key should verify for authentication");
+    let result = document.verify_with_usage(&login_key.public_key, b"challenge", &signature, KeyUsage::AssertionMethod);
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_key_agreement_key_matches_keypair_derivation() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let (_, x25519_public) = keypair.to_x25519_keypair().expect("Should derive X25519 key");
+
+    let document = DidDocumentBuilder::new(&did)
+        .verification_method(key_method(&did, "keys-1"))
+        .key_agreement_key(format!("{}#keys-2", did), &did, &x25519_public.into())
+        .build();
+    assert_eq!(document.key_agreement, vec![format!("{}#keys-2", did)]);
+    assert_eq!(document.verification_method[1].method_type, "X25519KeyAgreementKey2020");
+
+    let parsed = DidDocument::from_json(&document.to_json().expect("Should serialize"))
+        .expect("Should parse document");
+    let keys: Vec<KeyAgreementPublicKey> = parsed.key_agreement_keys().expect("Should recover key agreement keys");
+    assert_eq!(keys, vec![KeyAgreementPublicKey::from(x25519_public)]);
+    assert_eq!(X25519Public::from(keys[0]), x25519_public);
+}
+
+#[test]
+fn test_key_agreement_keys_reject_wrong_method_type() {
+    let did = Did::from("did:dverse:placeholder");
+    let method = key_method(&did, "keys-1");
+    let document = DidDocumentBuilder::new(&did)
+        .verification_method(method.clone())
+        .key_agreement(method.id)
+        .build();
+    assert!(matches!(document.key_agreement_keys(), Err(IdentityError::InvalidDocument(_))));
+}
+
+#[test]
+fn test_dual_key_did_document_lists_key_agreement() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_keypair_with_encryption(&keypair).expect("Should derive dual-key DID");
+    let (_, x25519_public) = keypair.to_x25519_keypair().expect("Should derive X25519 key");
+
+    let document = did.to_did_document().expect("Should build document");
+    assert_eq!(document.key_agreement_keys().expect("Should recover key"), vec![x25519_public.into()]);
+
+    let plain = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    assert!(plain.to_did_document().expect("Should build document").key_agreement.is_empty());
+}
+
+#[test]
+fn test_self_proof_verifies_and_detects_changes() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let document = did.to_did_document().expect("Should build document");
+    let method_id = format!("{}#keys-1", did);
+
+    assert!(matches!(document.verify_self_proof(), Err(IdentityError::InvalidDocument(_))));
+
+    let signed = document.with_self_proof(&keypair, &method_id).expect("Should sign document");
+    signed.verify_self_proof().expect("Self proof should verify");
+    let parsed = DidDocument::from_json(&signed.to_json().expect("Should serialize")).expect("Should parse");
+    parsed.verify_self_proof().expect("Self proof should survive JSON round trip");
+
+    let mut tampered = signed.clone();
+    tampered.also_known_as.push("did:web:attacker.example".to_string());
+    assert!(tampered.verify_self_proof().is_err());
+
+    let other = KeyPair::generate().expect("Should generate keypair");
+    assert!(matches!(document.with_self_proof(&other, &method_id), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_self_proof_covers_unknown_members() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let method_id = format!("{}#keys-1", did);
+    let mut document = did.to_did_document().expect("Should build document");
+    document.extra.insert("controller".to_string(), serde_json::json!(did.to_string()));
+    document.verification_method[0].extra.insert("revoked".to_string(), serde_json::json!(false));
+    let signed = document.with_self_proof(&keypair, &method_id).expect("Should sign document");
+
+    // Members the types do not model survive parsing and are covered by the proof.
+    let parsed = DidDocument::from_json(&signed.to_json().expect("Should serialize")).expect("Should parse");
+    assert_eq!(parsed, signed);
+    parsed.verify_self_proof().expect("Proof should cover unknown members");
+
+    let mut value: serde_json::Value =
+        serde_json::from_str(&signed.to_json().expect("Should serialize")).expect("Should be JSON");
+    value["controller"] = serde_json::json!("did:dverse:attacker");
+    let tampered = DidDocument::from_json(&value.to_string()).expect("Should parse");
+    assert!(tampered.verify_self_proof().is_err());
+
+    let mut value: serde_json::Value =
+        serde_json::from_str(&signed.to_json().expect("Should serialize")).expect("Should be JSON");
+    value["verificationMethod"][0]["revoked"] = serde_json::json!(true);
+    let tampered = DidDocument::from_json(&value.to_string()).expect("Should parse");
+    assert!(tampered.verify_self_proof().is_err());
+}
+
+#[test]
+fn test_self_proof_requires_authorized_method() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let method_id = format!("{}#keys-1", did);
+    let signed = did
+        .to_did_document()
+        .expect("Should build document")
+        .with_self_proof(&keypair, &method_id)
+        .expect("Should sign document");
+
+    // Declared but not listed under authentication or assertionMethod.
+    let mut unlisted = signed.clone();
+    unlisted.authentication.clear();
+    unlisted.assertion_method.clear();
+    assert!(matches!(unlisted.verify_self_proof(), Err(IdentityError::InvalidDocument(_))));
+    assert!(matches!(
+        unlisted.with_self_proof(&keypair, &method_id),
+        Err(IdentityError::InvalidDocument(_))
+    ));
+}
+
+#[test]
+fn test_self_proof_referencing_missing_key_is_rejected() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let signed = did
+        .to_did_document()
+        .expect("Should build document")
+        .with_self_proof(&keypair, &format!("{}#keys-1", did))
+        .expect("Should sign document");
+
+    let mut missing = signed.clone();
+    if let Some(proof) = missing.proof.as_mut() {
+        proof.verification_method = format!("{}#keys-9", did);
+    }
+    assert!(matches!(missing.verify_self_proof(), Err(IdentityError::InvalidDocument(_))));
+
+    // Dropping the key from the document also removes the method the proof points at.
+    let mut without_key = signed.clone();
+    without_key.verification_method.clear();
+    assert!(matches!(without_key.verify_self_proof(), Err(IdentityError::InvalidDocument(_))));
+}
@@ -0,0 +1,103 @@
+use dverse_identity::{ExtendedKeyPair, IdentityError};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// SLIP-0010 test vector 1 for ed25519.
+const SEED: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+#[test]
+fn test_slip10_master_and_child_match_vector() {
+    let master = ExtendedKeyPair::from_seed(&SEED).expect("Should derive master key");
+    assert_eq!(hex(master.chain_code()), "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb");
+    assert_eq!(
+        hex(master.keypair().private_key.as_bytes()),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+
+    let child = master.derive_extended(0).expect("Should derive m/0'");
+    assert_eq!(hex(child.chain_code()), "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69");
+    assert_eq!(
+        hex(child.keypair().private_key.as_bytes()),
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    );
+}
+
+#[test]
+fn test_to_extended_child_derivation_is_deterministic() {
+    let master = ExtendedKeyPair::from_seed(&SEED).expect("Should derive master key");
+    let rebuilt = master
+        .keypair()
+        .to_extended(*master.chain_code())
+        .expect("Should extend keypair");
+
+    let first = rebuilt.derive_extended(7).expect("Should derive child");
+    let second = master.derive_extended(7).expect("Should derive child");
+    assert_eq!(first, second);
+    assert_ne!(first.chain_code(), master.chain_code());
+    assert_ne!(first, master.derive_extended(8).expect("Should derive sibling"));
+
+    let signature = first.keypair().sign(b"wallet").expect("Child key should sign");
+    first.keypair().verify(b"wallet", &signature).expect("Child signature should verify");
+}
+
+#[test]
+fn test_derive_extended_rejects_pre_hardened_index() {
+    let master = ExtendedKeyPair::from_seed(&SEED).expect("Should derive master key");
+    assert!(matches!(master.derive_extended(0x8000_0000), Err(IdentityError::InvalidArgument(_))));
+}
+
+#[test]
+fn test_slip10_vector_1_full_path_with_derive_child() {
+    // (index, chain code, private key) for m/0H/1H/2H/2H/1000000000H.
+    let path = [
+        (
+            0x8000_0000,
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        ),
+        (
+            0x8000_0001,
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        ),
+        (
+            0x8000_0002,
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+        ),
+        (
+            0x8000_0002,
+            "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+        ),
+        (
+            0x8000_0000 + 1_000_000_000,
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        ),
+    ];
+
+    let mut node = ExtendedKeyPair::from_seed(&SEED).expect("Should derive master key");
+    for (index, chain_code, private_key) in path {
+        node = node.derive_child(index).expect("Should derive hardened child");
+        assert_eq!(hex(node.chain_code()), chain_code);
+        assert_eq!(hex(node.keypair().private_key.as_bytes()), private_key);
+    }
+
+    let signature = node.keypair().sign(b"leaf").expect("Leaf key should sign");
+    node.keypair().verify(b"leaf", &signature).expect("Leaf signature should verify");
+}
+
+#[test]
+fn test_derive_child_rejects_non_hardened_index() {
+    let master = ExtendedKeyPair::from_seed(&SEED).expect("Should derive master key");
+    for index in [0, 1, 0x7fff_ffff] {
+        assert!(matches!(master.derive_child(index), Err(IdentityError::InvalidArgument(_))));
+    }
+    assert_eq!(
+        master.derive_child(0x8000_0005).expect("Should derive hardened child"),
+        master.derive_extended(5).expect("Should derive hardened child")
+    );
+}
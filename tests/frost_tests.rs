@@ -0,0 +1,199 @@
+use dverse_identity::frost::{self, KeyShare, Round1Package, Round2Package, SignatureShare, SigningCommitment};
+use dverse_identity::{IdentityError, KeyPair, PrivateKey};
+
+fn run_dkg(max_signers: u16, min_signers: u16) -> Vec<KeyShare> {
+    let mut round1_secrets = Vec::new();
+    let mut round1_packages: Vec<Round1Package> = Vec::new();
+    for id in 1..=max_signers {
+        let (secret, package) = frost::dkg_part1(id, max_signers, min_signers).expect("DKG part 1 should succeed");
+        round1_secrets.push(secret);
+        round1_packages.push(package);
+    }
+
+    let others = |id: u16| -> Vec<Round1Package> {
+        round1_packages.iter().filter(|p| p.sender != id).cloned().collect()
+    };
+
+    let mut round2_secrets = Vec::new();
+    let mut round2_packages: Vec<Round2Package> = Vec::new();
+    for (secret, id) in round1_secrets.into_iter().zip(1..=max_signers) {
+        let (secret, packages) = frost::dkg_part2(secret, &others(id)).expect("DKG part 2 should succeed");
+        round2_secrets.push(secret);
+        round2_packages.extend(packages);
+    }
+
+    round2_secrets
+        .into_iter()
+        .zip(1..=max_signers)
+        .map(|(secret, id)| {
+            let received: Vec<Round2Package> = round2_packages.iter().filter(|p| p.receiver == id).cloned().collect();
+            frost::dkg_part3(secret, &others(id), &received).expect("DKG part 3 should succeed")
+        })
+        .collect()
+}
+
+#[test]
+fn test_frost_dkg_agrees_on_group_key() {
+    let shares = run_dkg(3, 2);
+    let package = shares[0].public_key_package();
+    for share in &shares {
+        assert_eq!(share.public_key_package(), package);
+    }
+    assert_eq!(package.group_public_key().as_bytes().len(), 32);
+}
+
+#[test]
+fn test_frost_two_of_three_signature_verifies_as_ed25519() {
+    let shares = run_dkg(3, 2);
+    let package = shares[0].public_key_package().clone();
+    let message = b"Threshold-signed D-Verse message";
+
+    // Participants 1 and 3 sign; participant 2 sits out.
+    let signers = [&shares[0], &shares[2]];
+    let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|share| frost::commit(share)).unzip();
+    let signature_shares: Vec<_> = signers
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonces)| frost::sign(share, nonces, message, &commitments).expect("Should produce share"))
+        .collect();
+
+    let signature = frost::aggregate(&package, message, &commitments, &signature_shares).expect("Should aggregate");
+    assert_eq!(signature.len(), 64);
+
+    let verifier = KeyPair {
+        private_key: PrivateKey::from_bytes(vec![0; 32]), // Dummy private key, not used for verification
+        public_key: package.group_public_key(),
+    };
+    verifier.verify(message, &signature).expect("Aggregated signature should verify as Ed25519");
+    assert!(verifier.verify(b"a different message", &signature).is_err());
+}
+
+#[test]
+fn test_frost_rejects_insufficient_shares() {
+    let shares = run_dkg(3, 2);
+    let package = shares[0].public_key_package().clone();
+    let message = b"Not enough signers";
+
+    let (nonces, commitment) = frost::commit(&shares[1]);
+    let commitments = [commitment];
+
+    let result = frost::sign(&shares[1], nonces, message, &commitments);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+
+    let result = frost::aggregate(&package, message, &commitments, &[]);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+}
+
+#[test]
+fn test_frost_rejects_tampered_share() {
+    let shares = run_dkg(3, 2);
+    let package = shares[0].public_key_package().clone();
+    let message = b"Tampered share";
+
+    let signers = [&shares[0], &shares[1]];
+    let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|share| frost::commit(share)).unzip();
+    let mut signature_shares: Vec<_> = signers
+        .iter()
+        .zip(nonces)
+        .map(|(share, nonces)| frost::sign(share, nonces, message, &commitments).unwrap())
+        .collect();
+
+    // A share computed for another message must be caught during aggregation.
+    let (other_nonces, _) = frost::commit(&shares[1]);
+    signature_shares[1] = frost::sign(&shares[1], other_nonces, b"other", &commitments).unwrap();
+
+    let result = frost::aggregate(&package, message, &commitments, &signature_shares);
+    assert!(matches!(result, Err(IdentityError::SignatureError(_))));
+}
+
+#[test]
+fn test_frost_round2_package_debug_hides_share() {
+    let (secret_1, _) = frost::dkg_part1(1, 2, 2).expect("DKG part 1 should succeed");
+    let (_, package_2) = frost::dkg_part1(2, 2, 2).expect("DKG part 1 should succeed");
+    let (_, packages) = frost::dkg_part2(secret_1, &[package_2]).expect("DKG part 2 should succeed");
+
+    let debug = format!("{:?}", packages[0]);
+    assert_eq!(debug, "Round2Package { sender: 1, receiver: 2, .. }");
+}
+
+#[test]
+fn test_frost_dkg_rejects_small_order_commitments() {
+    const IDENTITY: [u8; 32] = {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes
+    };
+    // The point (0, -1), of order 2.
+    const ORDER_TWO: [u8; 32] = {
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0xec;
+        bytes[31] = 0x7f;
+        bytes
+    };
+
+    let (secret, honest) = frost::dkg_part1(1, 2, 2).expect("DKG part 1 should succeed");
+    let (honest_r, honest_mu) = honest.proof_bytes();
+    let round_trip =
+        Round1Package::from_bytes(1, &honest.commitment_bytes(), &honest_r, &honest_mu).expect("Should rebuild package");
+    assert_eq!(round_trip.commitment_bytes(), honest.commitment_bytes());
+
+    // An all-identity package with R = identity and mu = 0 satisfies the proof of
+    // knowledge equation, so it must be rejected for its points alone.
+    let identity = Round1Package::from_bytes(2, &[IDENTITY, IDENTITY], &IDENTITY, &[0u8; 32])
+        .expect("Identity points decompress");
+    let result = frost::dkg_part2(secret, &[identity]);
+    assert!(matches!(result, Err(IdentityError::KeyGenerationError(_))));
+
+    let (secret, _) = frost::dkg_part1(1, 2, 2).expect("DKG part 1 should succeed");
+    let (_, other) = frost::dkg_part1(2, 2, 2).expect("DKG part 1 should succeed");
+    let (other_r, other_mu) = other.proof_bytes();
+    let mut commitments = other.commitment_bytes();
+    commitments[1] = ORDER_TWO;
+    let small_order = Round1Package::from_bytes(2, &commitments, &other_r, &other_mu).expect("Point decompresses");
+    let result = frost::dkg_part2(secret, &[small_order]);
+    assert!(matches!(result, Err(IdentityError::KeyGenerationError(_))));
+}
+
+#[test]
+fn test_frost_wire_encodings_reject_bad_points_and_scalars() {
+    // The point (0, -1), of order 2, and the group order L, the smallest non-canonical scalar.
+    const ORDER_TWO: [u8; 32] = {
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0xec;
+        bytes[31] = 0x7f;
+        bytes
+    };
+    const IDENTITY: [u8; 32] = {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        bytes
+    };
+    const GROUP_ORDER: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+    ];
+
+    let shares = run_dkg(2, 2);
+    let message = b"Wire encodings";
+    let (nonces, commitment) = frost::commit(&shares[0]);
+    let (hiding, binding) = (commitment.hiding_bytes(), commitment.binding_bytes());
+    let rebuilt = SigningCommitment::from_bytes(1, &hiding, &binding).expect("Should rebuild commitment");
+    assert_eq!(rebuilt, commitment);
+    for (bad_hiding, bad_binding) in [(IDENTITY, binding), (hiding, ORDER_TWO)] {
+        let result = SigningCommitment::from_bytes(1, &bad_hiding, &bad_binding);
+        assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+    }
+
+    let (_, other_commitment) = frost::commit(&shares[1]);
+    let share = frost::sign(&shares[0], nonces, message, &[commitment, other_commitment]).expect("Should produce share");
+    let rebuilt = SignatureShare::from_bytes(share.identifier, &share.to_bytes()).expect("Should rebuild share");
+    assert_eq!(rebuilt, share);
+    assert!(matches!(SignatureShare::from_bytes(1, &GROUP_ORDER), Err(IdentityError::InvalidKey(_))));
+
+    let (secret_1, _) = frost::dkg_part1(1, 2, 2).expect("DKG part 1 should succeed");
+    let (_, package_2) = frost::dkg_part1(2, 2, 2).expect("DKG part 1 should succeed");
+    let (_, packages) = frost::dkg_part2(secret_1, &[package_2]).expect("DKG part 2 should succeed");
+    let rebuilt = Round2Package::from_bytes(1, 2, &packages[0].to_bytes()).expect("Should rebuild package");
+    assert_eq!(*rebuilt.to_bytes(), *packages[0].to_bytes());
+    assert!(matches!(Round2Package::from_bytes(1, 2, &GROUP_ORDER), Err(IdentityError::InvalidKey(_))));
+}
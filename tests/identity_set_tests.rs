@@ -0,0 +1,29 @@
+use dverse_identity::{IdentitySet, KeyPair};
+
+#[test]
+fn test_identity_set_from_keypairs_verifies_with_each() {
+    let keypairs: Vec<KeyPair> = (0..3).map(|_| KeyPair::generate().expect("Should generate keypair")).collect();
+    let set: IdentitySet = keypairs.iter().collect();
+
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.primary(), Some(&keypairs[0].public_key));
+
+    for keypair in &keypairs {
+        let signature = keypair.sign(b"device message").expect("Should sign");
+        set.verify_any(b"device message", &signature).expect("Each device should verify");
+    }
+
+    let outsider = KeyPair::generate().expect("Should generate keypair");
+    let signature = outsider.sign(b"device message").expect("Should sign");
+    assert!(set.verify_any(b"device message", &signature).is_err());
+}
+
+#[test]
+fn test_identity_set_from_owned_keypairs_skips_duplicates() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let set: IdentitySet = vec![keypair.clone(), keypair.clone()].into_iter().collect();
+
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.primary(), Some(&keypair.public_key));
+    assert!(IdentitySet::from_iter(Vec::<KeyPair>::new()).primary().is_none());
+}
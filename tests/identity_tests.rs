@@ -3,6 +3,8 @@ use dverse_identity::{
     Did,
     IdentityError,
     PrivateKey,
+    SignatureDomain,
+    VerifyPolicy,
 };
 
 #[test]
@@ -12,6 +14,73 @@ fn test_keypair_generation() {
     assert_eq!(keypair.public_key.as_bytes().len(), 32, "Public key should be 32 bytes");
 }
 
+// Fills every request with the same byte, standing in for a platform RNG.
+struct FixedRng(u8);
+
+impl rand_core::RngCore for FixedRng {
+    fn next_u32(&mut self) -> u32 {
+        u32::from_ne_bytes([self.0; 4])
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::from_ne_bytes([self.0; 8])
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(self.0);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand_core::CryptoRng for FixedRng {}
+
+#[test]
+fn test_keypair_generate_with_rng_uses_supplied_entropy() {
+    let keypair = KeyPair::generate_with_rng(&mut FixedRng(7)).expect("Should generate keypair");
+    assert_eq!(keypair, KeyPair::from_seed(&[7u8; 32]).expect("Should build keypair from seed"));
+
+    let random = KeyPair::generate_with_rng(&mut rand_core::OsRng).expect("Should generate keypair");
+    let signature = random.sign(b"rng").expect("Should sign");
+    random.verify(b"rng", &signature).expect("Should verify");
+}
+
+// Fails every request, like an OS generator that is unavailable in a sandbox.
+struct FailingRng;
+
+impl rand_core::RngCore for FailingRng {
+    fn next_u32(&mut self) -> u32 {
+        panic!("Infallible RNG method should not be used")
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        panic!("Infallible RNG method should not be used")
+    }
+
+    fn fill_bytes(&mut self, _dest: &mut [u8]) {
+        panic!("Infallible RNG method should not be used")
+    }
+
+    fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        Err(rand_core::Error::from(std::num::NonZeroU32::new(rand_core::Error::CUSTOM_START).unwrap()))
+    }
+}
+
+impl rand_core::CryptoRng for FailingRng {}
+
+#[test]
+fn test_keypair_generate_with_failing_rng_returns_error() {
+    match KeyPair::generate_with_rng(&mut FailingRng) {
+        Err(IdentityError::KeyGenerationError(msg)) => {
+            assert!(msg.contains("Random number generator failed"), "Unexpected message: {}", msg)
+        }
+        other => panic!("Expected KeyGenerationError, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_sign_and_verify_success() {
     let keypair = KeyPair::generate().expect("Should sign message");
@@ -80,14 +149,17 @@ fn test_did_roundtrip_sign_verify() {
     let signature = keypair.sign(message).expect("Should sign message");
 
     let recovered_public_key = did.to_public_key().expect("Should recover public key from DID");
+    recovered_public_key.verify(message, &signature).expect("Signature should verify with recovered public key");
+    did.verify(message, &signature).expect("Signature should verify against the DID");
 
-    // Create a temporary KeyPair for verification using the recovered public key
-    let temp_keypair_for_verification = KeyPair {
-        private_key: PrivateKey::from_bytes(vec![0; 32]), // Dummy private key, not used for verification
-        public_key: recovered_public_key,
-    };
-
-    temp_keypair_for_verification.verify(message, &signature).expect("Signature should verify with recovered public key");
+    assert!(did.verify(b"Another message.", &signature).is_err());
+    let other = Did::from_public_key(&KeyPair::generate().expect("Should generate keypair").public_key)
+        .expect("Should derive DID");
+    assert!(other.verify(message, &signature).is_err());
+    assert!(matches!(
+        Did::from("not:a:did").verify(message, &signature),
+        Err(IdentityError::InvalidDidFormat(_))
+    ));
 }
 
 #[test]
@@ -113,6 +185,63 @@ fn test_did_to_public_key_invalid_format() {
     assert!(matches!(result.unwrap_err(), IdentityError::UnsupportedMulticodec(_)));
 }
 
+#[test]
+fn test_did_new_validates_eagerly() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let valid = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let checked = Did::new(valid.as_str()).expect("Valid DID should be accepted");
+    assert_eq!(checked, valid);
+    assert_eq!(Did::new(format!(" {}\n", valid)).expect("Should trim whitespace"), valid);
+
+    assert!(matches!(Did::new("garbage"), Err(IdentityError::InvalidDidFormat(_))));
+    assert!(matches!(Did::new("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"), Err(IdentityError::InvalidDidFormat(_))));
+    assert!(matches!(Did::new("did:dverse:z6NABC"), Err(IdentityError::UnsupportedMulticodec(_))));
+
+    let short = dverse_identity::PublicKey::from_bytes(keypair.public_key.as_bytes()[..31].to_vec());
+    let short_did = Did::from_public_key(&short).expect("Encoding does not validate the key");
+    assert!(matches!(Did::new(short_did.as_str()), Err(IdentityError::InvalidKey(_))));
+
+    // The unchecked conversion still wraps anything.
+    assert_eq!(Did::from("garbage").as_str(), "garbage");
+}
+
+#[test]
+fn test_did_new_rejects_non_canonical_encodings() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let valid = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let binary = valid.to_binary().expect("Should convert to binary");
+
+    for base in [multibase::Base::Base16Lower, multibase::Base::Base64Url, multibase::Base::Base32Lower] {
+        let spelling = format!("did:dverse:{}", multibase::encode(base, binary));
+        assert!(
+            matches!(Did::new(spelling.as_str()), Err(IdentityError::UnsupportedMultibase(_))),
+            "{} should be rejected",
+            spelling
+        );
+        assert!(matches!(spelling.parse::<Did>(), Err(IdentityError::UnsupportedMultibase(_))));
+    }
+}
+
+#[test]
+fn test_did_parse_is_checked() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let valid = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let parsed: Did = valid.as_str().parse().expect("Valid DID should parse");
+    assert_eq!(parsed, valid);
+
+    assert!(matches!("garbage".parse::<Did>(), Err(IdentityError::InvalidDidFormat(_))));
+    assert!(matches!("did:dverse:z6NABC".parse::<Did>(), Err(IdentityError::UnsupportedMulticodec(_))));
+
+    fn parse_in_result(s: &str) -> dverse_identity::Result<Did> {
+        let did: Did = s.parse()?;
+        Ok(did)
+    }
+    assert!(parse_in_result(valid.as_str()).is_ok());
+    assert!(parse_in_result("did:dverse:").is_err());
+}
+
 #[test]
 fn test_did_display_and_from_str() {
     let did_str = "did:dverse:z6Mkk...test";
@@ -123,3 +252,1276 @@ fn test_did_display_and_from_str() {
     let did_from_string: Did = did_str.to_string().into();
     assert_eq!(did_from_string.as_str(), did_str);
 }
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_verify_parallel_matches_sequential() {
+    let mut keypairs = Vec::new();
+    let mut items = Vec::new();
+    for i in 0..200 {
+        let keypair = KeyPair::generate().expect("Should generate keypair");
+        let message = format!("message {}", i).into_bytes();
+        let mut signature = keypair.sign(&message).expect("Should sign message");
+        if i % 7 == 0 {
+            signature[0] ^= 0x01; // Corrupt every seventh signature
+        }
+        items.push((keypair.public_key.clone(), message, signature));
+        keypairs.push(keypair);
+    }
+
+    let parallel = dverse_identity::verify_parallel(&items);
+    assert_eq!(parallel.len(), items.len());
+
+    for (i, ((keypair, (_, message, signature)), result)) in keypairs.iter().zip(&items).zip(&parallel).enumerate() {
+        let sequential = keypair.verify(message, signature);
+        assert_eq!(sequential.is_ok(), result.is_ok(), "Result mismatch at index {}", i);
+        assert_eq!(result.is_ok(), i % 7 != 0);
+    }
+}
+
+#[test]
+fn test_did_avatar_seed_is_deterministic() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let same_did = Did::from(did.as_str());
+
+    let seed = did.avatar_seed().expect("Should derive avatar seed");
+    assert_eq!(seed, did.avatar_seed().expect("Should derive avatar seed"));
+    assert_eq!(seed, same_did.avatar_seed().expect("Should derive avatar seed"));
+}
+
+#[test]
+fn test_did_avatar_seed_differs_between_keys() {
+    let did_a = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+    let did_b = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+
+    assert_ne!(did_a.avatar_seed().unwrap(), did_b.avatar_seed().unwrap());
+}
+
+#[test]
+fn test_did_theme_color_is_deterministic() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let same_did = Did::from(did.as_str().to_string());
+
+    let color = did.theme_color().expect("Should derive theme color");
+    assert_eq!(color, did.theme_color().expect("Should derive theme color"));
+    assert_eq!(color, same_did.theme_color().expect("Should derive theme color"));
+}
+
+#[test]
+fn test_did_theme_colors_are_varied_and_usable() {
+    let colors: Vec<[u8; 3]> = (0..32)
+        .map(|_| {
+            let keypair = KeyPair::generate().expect("Should generate keypair");
+            Did::from_public_key(&keypair.public_key).unwrap().theme_color().unwrap()
+        })
+        .collect();
+
+    for [r, g, b] in &colors {
+        let (max, min) = (*r.max(g).max(b), *r.min(g).min(b));
+        assert!(max - min >= 40, "Color {:?} is too grey", [r, g, b]);
+        assert!(max >= 100 && min <= 200, "Color {:?} is too dark or too light", [r, g, b]);
+    }
+
+    // Bucket by dominant channel: 32 random keys should not all land on the same one.
+    let dominant = |c: &[u8; 3]| (0..3).max_by_key(|&i| c[i]).unwrap();
+    let first = dominant(&colors[0]);
+    assert!(colors.iter().any(|c| dominant(c) != first));
+    let mut distinct = colors.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert!(distinct.len() > 24);
+}
+
+#[test]
+fn test_did_same_key_any_network() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let mainnet = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let encoded_key = mainnet.as_str().trim_start_matches("did:dverse:");
+    let testnet = Did::from(format!("did:dverse:testnet:{}", encoded_key));
+    let devnet = Did::from(format!("did:dverse:devnet:{}", encoded_key));
+
+    assert!(mainnet.same_key_any_network(&testnet).expect("Should compare DIDs"));
+    assert!(testnet.same_key_any_network(&devnet).expect("Should compare DIDs"));
+}
+
+#[test]
+fn test_did_same_key_any_network_different_keys() {
+    let did_a = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+    let did_b = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+    let testnet_a = Did::from(format!("did:dverse:testnet:{}", did_a.as_str().trim_start_matches("did:dverse:")));
+    let testnet_b = Did::from(format!("did:dverse:testnet:{}", did_b.as_str().trim_start_matches("did:dverse:")));
+
+    assert!(!testnet_a.same_key_any_network(&testnet_b).expect("Should compare DIDs"));
+
+    let malformed = Did::from("did:dverse:test net:z6Mk");
+    assert!(matches!(malformed.same_key_any_network(&testnet_a), Err(IdentityError::InvalidDidFormat(_))));
+}
+
+#[test]
+fn test_sign_chunks_matches_concatenated_bytes() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let chunks: [&[u8]; 3] = [b"header|", b"body|", b"trailer"];
+    let concatenated = chunks.concat();
+
+    let chunked_signature = keypair.sign_chunks(chunks).expect("Should sign chunks");
+    let whole_signature = keypair.sign_chunks([concatenated.as_slice()]).expect("Should sign whole buffer");
+    assert_eq!(chunked_signature, whole_signature);
+
+    keypair.verify_chunks([b"head".as_slice(), b"er|body|trailer"], &chunked_signature)
+        .expect("Differently split chunks should verify");
+    assert!(keypair.verify_chunks([b"header|body|".as_slice()], &chunked_signature).is_err());
+
+    // Ed25519ph signatures are domain-separated from plain Ed25519 signatures.
+    assert!(keypair.verify(&concatenated, &chunked_signature).is_err());
+}
+
+#[test]
+fn test_keypair_into_parts_and_public_key_conversion() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let expected_private = keypair.private_key.clone();
+    let expected_public = keypair.public_key.clone();
+
+    let borrowed: dverse_identity::PublicKey = (&keypair).into();
+    assert_eq!(borrowed, expected_public);
+
+    let (private_key, public_key) = keypair.clone().into_parts();
+    assert_eq!(private_key, expected_private);
+    assert_eq!(public_key, expected_public);
+
+    let owned: dverse_identity::PublicKey = keypair.into();
+    assert_eq!(owned, expected_public);
+}
+
+// Adds the Ed25519 group order L to the little-endian `s` half of a signature, producing
+// the non-reduced form some legacy signers emitted.
+fn unreduce_signature(signature: &[u8]) -> Vec<u8> {
+    const L: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+    ];
+    let mut result = signature.to_vec();
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let sum = result[32 + i] as u16 + L[i] as u16 + carry;
+        result[32 + i] = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0);
+    result
+}
+
+#[test]
+fn test_verify_policy_legacy_accepts_non_reduced_signature() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Signed by an old library";
+    let signature = keypair.sign(message).expect("Should sign message");
+    let legacy_signature = unreduce_signature(&signature);
+
+    for policy in [VerifyPolicy::Strict, VerifyPolicy::Lenient, VerifyPolicy::Legacy] {
+        keypair.verify_with_policy(message, &signature, policy).expect("Canonical signature should verify");
+    }
+    assert_eq!(VerifyPolicy::default(), VerifyPolicy::Strict);
+
+    assert!(keypair.verify_with_policy(message, &legacy_signature, VerifyPolicy::Strict).is_err());
+    assert!(keypair.verify_with_policy(message, &legacy_signature, VerifyPolicy::Lenient).is_err());
+    keypair.verify_with_policy(message, &legacy_signature, VerifyPolicy::Legacy)
+        .expect("Legacy policy should accept the non-reduced signature");
+    assert!(keypair.verify_with_policy(b"other", &legacy_signature, VerifyPolicy::Legacy).is_err());
+}
+
+#[test]
+fn test_derive_storage_key_is_deterministic() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let first = keypair.derive_storage_key(b"notes-db").expect("Should derive storage key");
+    let second = keypair.derive_storage_key(b"notes-db").expect("Should derive storage key");
+    assert_eq!(*first, *second);
+
+    let other_identity = KeyPair::generate().expect("Should generate keypair");
+    assert_ne!(*first, *other_identity.derive_storage_key(b"notes-db").unwrap());
+}
+
+#[test]
+fn test_derive_storage_key_differs_across_labels() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let notes = keypair.derive_storage_key(b"notes-db").unwrap();
+    let photos = keypair.derive_storage_key(b"photos-db").unwrap();
+    assert_ne!(*notes, *photos);
+}
+
+#[test]
+fn test_verify_batch_report_lists_failure_indices() {
+    let keypairs: Vec<KeyPair> = (0..8).map(|_| KeyPair::generate().unwrap()).collect();
+    let messages: Vec<Vec<u8>> = (0..8).map(|i| format!("record {}", i).into_bytes()).collect();
+    let mut signatures: Vec<Vec<u8>> = keypairs.iter().zip(&messages).map(|(k, m)| k.sign(m).unwrap()).collect();
+
+    signatures[1][5] ^= 0x10; // Corrupted signature
+    signatures[4].truncate(10); // Malformed signature
+    signatures[6] = keypairs[0].sign(&messages[6]).unwrap(); // Signed by the wrong key
+
+    let items: Vec<(dverse_identity::PublicKey, &[u8], &[u8])> = keypairs
+        .iter()
+        .zip(&messages)
+        .zip(&signatures)
+        .map(|((k, m), s)| (k.public_key.clone(), m.as_slice(), s.as_slice()))
+        .collect();
+
+    let report = dverse_identity::verify_batch_report(&items);
+    assert_eq!(report.failures, vec![1, 4, 6]);
+    assert!(!report.all_valid());
+    assert_eq!(report.failure_count(), 3);
+    assert_eq!(report.valid_count(), 5);
+    assert!(report.is_failure(4));
+    assert!(!report.is_failure(0));
+
+    assert!(dverse_identity::verify_batch_report(&items[7..]).all_valid());
+}
+
+#[test]
+fn test_did_with_encryption_key_roundtrip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_keypair_with_encryption(&keypair).expect("Should derive dual-key DID");
+    assert!(did.as_str().starts_with("did:dverse:z"));
+
+    assert_eq!(did.signing_key().expect("Should recover signing key"), keypair.public_key);
+    assert_eq!(did.to_public_key().expect("Should recover signing key"), keypair.public_key);
+
+    // The advertised X25519 key is the Montgomery form of the Ed25519 key.
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(
+        keypair.public_key.as_bytes().try_into().unwrap(),
+    ).unwrap();
+    assert_eq!(did.key_agreement_key().expect("Should recover key agreement key"), verifying_key.to_montgomery().to_bytes());
+
+    let plain_did = Did::from_public_key(&keypair.public_key).unwrap();
+    assert!(matches!(plain_did.key_agreement_key(), Err(IdentityError::UnsupportedMulticodec(_))));
+}
+
+#[test]
+fn test_did_validate_syntax_accepts_conforming_dids() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    Did::validate_syntax(did.as_str()).expect("Derived DID should be syntactically valid");
+
+    for valid in ["did:example:123", "did:web:example.com%3A8443", "did:dverse:testnet:z6Mk", "did:key2:a_b-c.d"] {
+        Did::validate_syntax(valid).unwrap_or_else(|e| panic!("{} should be valid: {}", valid, e));
+    }
+}
+
+#[test]
+fn test_did_validate_syntax_rejects_illegal_characters() {
+    let invalid_methods = ["did:DVERSE:z6Mk", "did:dv_erse:z6Mk", "did::z6Mk", "did:dvérse:z6Mk"];
+    for invalid in invalid_methods {
+        let result = Did::validate_syntax(invalid);
+        assert!(matches!(result, Err(IdentityError::InvalidDidFormat(_))), "{} should be rejected", invalid);
+    }
+
+    let invalid_ids = [
+        "did:dverse:z6Mk/path",
+        "did:dverse:z6 Mk",
+        "did:dverse:z6Mk#frag",
+        "did:dverse:z6Mk%G1",
+        "did:dverse:z6Mk:",
+        "did:dverse:",
+        "did:dverse",
+        "dverse:z6Mk",
+    ];
+    for invalid in invalid_ids {
+        let result = Did::validate_syntax(invalid);
+        assert!(matches!(result, Err(IdentityError::InvalidDidFormat(_))), "{} should be rejected", invalid);
+    }
+}
+
+#[test]
+fn test_verify_self_contained_roundtrip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Self-verifying payload";
+    let mut blob = keypair.sign(message).expect("Should sign message");
+    blob.extend_from_slice(keypair.public_key.as_bytes());
+
+    let public_key = dverse_identity::verify_self_contained(message, &blob).expect("Blob should verify");
+    assert_eq!(public_key, keypair.public_key);
+
+    assert!(matches!(
+        dverse_identity::verify_self_contained(b"other message", &blob),
+        Err(IdentityError::DalekError(_))
+    ));
+}
+
+#[test]
+fn test_verify_self_contained_rejects_truncated_blob() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Self-verifying payload";
+    let mut blob = keypair.sign(message).expect("Should sign message");
+    blob.extend_from_slice(keypair.public_key.as_bytes());
+    blob.pop();
+
+    let result = dverse_identity::verify_self_contained(message, &blob);
+    assert!(matches!(result, Err(IdentityError::ArrayConversionError(_))));
+}
+
+#[test]
+fn test_verify_stream_mixed_records() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let mut records: Vec<dverse_identity::SignedMessage> = (0..5)
+        .map(|i| keypair.sign_message(format!("log line {}", i).as_bytes()).expect("Should sign record"))
+        .collect();
+    records[1].message = b"tampered log line".to_vec();
+    records[3].signature[10] ^= 0xff;
+
+    let results: Vec<_> = dverse_identity::verify_stream(records.clone().into_iter()).collect();
+    assert_eq!(results.len(), 5);
+    for (i, result) in results.iter().enumerate() {
+        match result {
+            Ok(record) => {
+                assert!(i != 1 && i != 3, "Tampered record {} should fail", i);
+                assert_eq!(record, &records[i]);
+            }
+            Err(_) => assert!(i == 1 || i == 3, "Valid record {} should verify", i),
+        }
+    }
+}
+
+#[test]
+fn test_versioned_signature_current_version_verifies() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Versioned message";
+    let versioned = keypair.sign_versioned(message).expect("Should sign message");
+
+    assert_eq!(versioned.len(), 66);
+    assert_eq!(versioned[0], dverse_identity::VersionedSignature::CURRENT_VERSION);
+    assert_eq!(versioned[1], 64);
+    keypair.verify_versioned(message, &versioned).expect("Versioned signature should verify");
+    assert!(keypair.verify_versioned(b"other", &versioned).is_err());
+}
+
+#[test]
+fn test_versioned_signature_rejects_unknown_version() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Versioned message";
+    let mut versioned = keypair.sign_versioned(message).expect("Should sign message");
+    versioned[0] += 1;
+
+    let result = keypair.verify_versioned(message, &versioned);
+    assert!(matches!(result, Err(IdentityError::UnsupportedSignatureVersion(_))));
+
+    versioned[0] -= 1;
+    versioned[1] = 63;
+    assert!(matches!(keypair.verify_versioned(message, &versioned), Err(IdentityError::DecodingError(_))));
+}
+
+#[test]
+fn test_private_key_try_sign() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"Key-centric signing";
+
+    let signature = keypair.private_key.try_sign(message).expect("Well-formed key should sign");
+    assert_eq!(signature.to_vec(), keypair.sign(message).expect("Should sign message"));
+    keypair.verify(message, signature.as_ref()).expect("Typed signature should verify");
+
+    let json = serde_json::to_string(&signature).expect("Should serialize signature");
+    let decoded: dverse_identity::Signature = serde_json::from_str(&json).expect("Should deserialize signature");
+    assert_eq!(decoded, signature);
+}
+
+#[test]
+fn test_private_key_try_sign_rejects_malformed_length() {
+    let malformed = PrivateKey::from_bytes(vec![7; 31]);
+    match malformed.try_sign(b"message") {
+        Err(IdentityError::InvalidKey(msg)) => assert!(msg.contains("32 bytes"), "Unhelpful error: {}", msg),
+        other => panic!("Expected InvalidKey, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_did_shard_is_stable() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let reparsed = Did::from(did.to_string());
+
+    let shard = did.shard(16).expect("Should compute shard");
+    assert!(shard < 16);
+    assert_eq!(shard, reparsed.shard(16).expect("Should compute shard"));
+    assert_eq!(did.shard(1).unwrap(), 0);
+    assert!(matches!(did.shard(0), Err(IdentityError::InvalidArgument(_))));
+}
+
+#[test]
+fn test_did_shard_is_roughly_uniform() {
+    const BUCKETS: u32 = 8;
+    const SAMPLES: usize = 1000;
+    let mut counts = [0usize; BUCKETS as usize];
+    for _ in 0..SAMPLES {
+        let did = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+        counts[did.shard(BUCKETS).unwrap() as usize] += 1;
+    }
+
+    // Expected 125 per bucket; these bounds are many standard deviations wide.
+    for (bucket, count) in counts.iter().enumerate() {
+        assert!((60..=190).contains(count), "Bucket {} has {} of {} samples", bucket, count, SAMPLES);
+    }
+}
+
+#[test]
+fn test_commitment_sign_and_open() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let preimage = b"bid=42;salt=8f3a1c";
+    let (commitment, signature) = keypair.sign_commitment(preimage).expect("Should commit");
+
+    dverse_identity::verify_commitment(&keypair.public_key, &commitment, &signature)
+        .expect("Commitment signature should verify");
+    assert!(dverse_identity::open_commitment(&commitment, preimage));
+}
+
+#[test]
+fn test_commitment_rejects_mismatched_preimage() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let (commitment, signature) = keypair.sign_commitment(b"bid=42;salt=8f3a1c").expect("Should commit");
+
+    assert!(!dverse_identity::open_commitment(&commitment, b"bid=43;salt=8f3a1c"));
+    assert!(!dverse_identity::open_commitment(&commitment[..16], b"bid=42;salt=8f3a1c"));
+
+    let other = KeyPair::generate().expect("Should generate keypair");
+    assert!(dverse_identity::verify_commitment(&other.public_key, &commitment, &signature).is_err());
+}
+
+#[test]
+fn test_salted_signatures_differ_and_verify() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"status: online";
+
+    let (first_salt, first_signature) = keypair.sign_salted(message).expect("Should sign salted");
+    let (second_salt, second_signature) = keypair.sign_salted(message).expect("Should sign salted");
+    assert_ne!(first_salt, second_salt);
+    assert_ne!(first_signature, second_signature);
+
+    dverse_identity::verify_salted(&keypair.public_key, message, &first_salt, &first_signature)
+        .expect("First salted signature should verify");
+    dverse_identity::verify_salted(&keypair.public_key, message, &second_salt, &second_signature)
+        .expect("Second salted signature should verify");
+    assert!(dverse_identity::verify_salted(&keypair.public_key, message, &second_salt, &first_signature).is_err());
+    assert!(dverse_identity::verify_salted(&keypair.public_key, message, &first_salt[..8], &first_signature).is_err());
+}
+
+#[test]
+fn test_domain_signature_rejected_in_other_domain() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let challenge = b"nonce=7c1e";
+
+    let signature = keypair.sign_in_domain(SignatureDomain::Login, challenge).expect("Should sign login");
+    keypair
+        .verify_in_domain(SignatureDomain::Login, challenge, &signature)
+        .expect("Login signature should verify as login");
+
+    assert!(keypair.verify_in_domain(SignatureDomain::Transaction, challenge, &signature).is_err());
+    assert!(keypair.verify_in_domain(SignatureDomain::Message, challenge, &signature).is_err());
+    assert!(keypair.verify_in_domain(SignatureDomain::Custom(b"login"), challenge, &signature).is_err());
+    assert!(keypair.verify(challenge, &signature).is_err());
+}
+
+#[test]
+fn test_custom_domains_are_distinct() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair
+        .sign_in_domain(SignatureDomain::Custom(b"app/vote"), b"yes")
+        .expect("Should sign in custom domain");
+
+    keypair
+        .verify_in_domain(SignatureDomain::Custom(b"app/vote"), b"yes", &signature)
+        .expect("Custom domain signature should verify");
+    assert!(keypair.verify_in_domain(SignatureDomain::Custom(b"app/vot"), b"eyes", &signature).is_err());
+    assert!(keypair.verify_in_domain(SignatureDomain::Custom(b"app/poll"), b"yes", &signature).is_err());
+}
+
+#[test]
+fn test_context_signature_rejected_under_other_context() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"transfer 10";
+
+    let signature = keypair.sign_with_context(message, b"A").expect("Should sign with context");
+    keypair
+        .verify_with_context(message, b"A", &signature)
+        .expect("Signature should verify under its context");
+
+    assert!(keypair.verify_with_context(message, b"B", &signature).is_err());
+    assert!(keypair.verify_with_context(message, b"", &signature).is_err());
+    assert!(keypair.verify(message, &signature).is_err());
+
+    let plain = keypair.sign(message).expect("Should sign");
+    assert!(keypair.verify_with_context(message, b"A", &plain).is_err());
+}
+
+#[test]
+fn test_context_signature_framing_is_documented_layout() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let (message, context) = (b"payload".as_slice(), b"proto/v1".as_slice());
+    let signature = keypair.sign_with_context(message, context).expect("Should sign with context");
+
+    let namespace = b"dverse-identity/domain/custom/";
+    let mut framed = ((namespace.len() + context.len()) as u64).to_be_bytes().to_vec();
+    framed.extend_from_slice(namespace);
+    framed.extend_from_slice(context);
+    framed.extend_from_slice(message);
+    keypair.verify(&framed, &signature).expect("Framed bytes should verify with plain verify");
+
+    // Moving bytes between context and message changes the length prefix.
+    assert!(keypair.verify_with_context(b"v1payload", b"proto/", &signature).is_err());
+}
+
+#[test]
+fn test_sign_fields_distinguishes_groupings() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let first: &[&[u8]] = &[b"ab", b"c"];
+    let second: &[&[u8]] = &[b"a", b"bc"];
+
+    let signature = keypair.sign_fields(first).expect("Should sign fields");
+    keypair.verify_fields(first, &signature).expect("Should verify same fields");
+
+    assert_ne!(signature, keypair.sign_fields(second).expect("Should sign fields"));
+    assert!(keypair.verify_fields(second, &signature).is_err());
+    assert!(keypair.verify_fields(&[b"abc"], &signature).is_err());
+    assert!(keypair.verify_fields(&[b"ab", b"c", b""], &signature).is_err());
+    assert!(keypair.verify(b"abc", &signature).is_err());
+}
+
+#[test]
+fn test_contact_bundle_round_trip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let bundle = keypair.export_bundle("Ada Lovelace").expect("Should export bundle");
+
+    assert!(bundle.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+
+    let (did, name) = dverse_identity::import_bundle(&bundle).expect("Should import bundle");
+    assert_eq!(did, Did::from_public_key(&keypair.public_key).expect("Should derive DID"));
+    assert_eq!(name, "Ada Lovelace");
+}
+
+#[test]
+fn test_contact_bundle_rejects_tampering() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let bundle = keypair.export_bundle("Ada Lovelace").expect("Should export bundle");
+
+    // Re-encode the bundle with a different display name but the original signature.
+    let (_, json) = multibase::decode(&bundle).expect("Bundle should be multibase");
+    let json = String::from_utf8(json).expect("Bundle should be JSON").replace("Ada Lovelace", "Mallory");
+    let tampered = multibase::encode(multibase::Base::Base64Url, json);
+    assert!(dverse_identity::import_bundle(&tampered).is_err());
+
+    // Flipping a character anywhere must also fail.
+    let mut corrupted = bundle.into_bytes();
+    let last = corrupted.len() - 5;
+    corrupted[last] = if corrupted[last] == b'A' { b'B' } else { b'A' };
+    assert!(dverse_identity::import_bundle(&String::from_utf8(corrupted).expect("ASCII")).is_err());
+}
+
+const ADMIN_DIDS: &[&str] = &[
+    "did:dverse:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw",
+    "did:dverse:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+];
+
+#[test]
+fn test_did_compares_against_static_admin_set() {
+    let incoming = Did::from("did:dverse:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw");
+    assert!(ADMIN_DIDS.iter().any(|admin| incoming == *admin));
+    assert!(ADMIN_DIDS[0] == incoming);
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let stranger = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    assert!(!ADMIN_DIDS.contains(&stranger.as_str()));
+    assert!(ADMIN_DIDS.iter().all(|admin| stranger != *admin));
+}
+
+#[test]
+fn test_verify_logged_returns_signer_did() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.sign(b"audit me").expect("Should sign");
+
+    let did = keypair.public_key.verify_logged(b"audit me", &signature).expect("Should verify");
+    assert_eq!(did, Did::from_public_key(&keypair.public_key).expect("Should derive DID"));
+    assert!(keypair.public_key.verify_logged(b"audit you", &signature).is_err());
+}
+
+#[test]
+fn test_migrate_keys_from_legacy_format() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let stored_public = serde_json::json!(keypair.public_key.as_bytes());
+    let stored_private = serde_json::json!(keypair.private_key.as_bytes());
+
+    let public_key = dverse_identity::PublicKey::migrate_from_legacy(&stored_public).expect("Should migrate public key");
+    let private_key = PrivateKey::migrate_from_legacy(&stored_private).expect("Should migrate private key");
+    assert_eq!(public_key, keypair.public_key);
+    assert_eq!(private_key, keypair.private_key);
+
+    let not_bytes = serde_json::json!([1, 2, 300]);
+    assert!(matches!(
+        dverse_identity::PublicKey::migrate_from_legacy(&not_bytes),
+        Err(IdentityError::DecodingError(_))
+    ));
+}
+
+#[test]
+fn test_keypair_serializes_keys_as_base58_strings() {
+    let keypair = KeyPair::from_seed(&[9u8; 32]).expect("Should build keypair from seed");
+    let value = serde_json::to_value(&keypair).expect("Should serialize keypair");
+
+    let private_key = value["private_key"].as_str().expect("Private key should be a string");
+    let public_key = value["public_key"].as_str().expect("Public key should be a string");
+    assert_eq!(private_key, multibase::encode(multibase::Base::Base58Btc, [9u8; 32]));
+    assert_eq!(public_key, multibase::encode(multibase::Base::Base58Btc, keypair.public_key.as_bytes()));
+
+    let decoded: KeyPair = serde_json::from_value(value).expect("Should deserialize keypair");
+    assert_eq!(decoded, keypair);
+}
+
+#[test]
+fn test_key_deserialization_rejects_malformed_strings() {
+    use dverse_identity::PublicKey;
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let short = multibase::encode(multibase::Base::Base58Btc, &keypair.public_key.as_bytes()[..31]);
+    let hex = multibase::encode(multibase::Base::Base16Lower, keypair.public_key.as_bytes());
+
+    for text in ["z0OIl", short.as_str(), hex.as_str(), ""] {
+        assert!(serde_json::from_value::<PublicKey>(serde_json::json!(text)).is_err(), "Accepted {:?}", text);
+        assert!(serde_json::from_value::<PrivateKey>(serde_json::json!(text)).is_err(), "Accepted {:?}", text);
+    }
+    let legacy = serde_json::json!(keypair.public_key.as_bytes());
+    assert!(serde_json::from_value::<PublicKey>(legacy).is_err());
+}
+
+#[test]
+fn test_legacy_serde_keeps_byte_array_form() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Stored {
+        #[serde(with = "dverse_identity::legacy_serde::keypair")]
+        keypair: KeyPair,
+        #[serde(with = "dverse_identity::legacy_serde::public_key")]
+        peer: dverse_identity::PublicKey,
+    }
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let peer = KeyPair::generate().expect("Should generate keypair").public_key;
+    let value = serde_json::to_value(Stored { keypair: keypair.clone(), peer: peer.clone() }).expect("Should serialize");
+
+    assert_eq!(value["keypair"]["private_key"], serde_json::json!(keypair.private_key.as_bytes()));
+    assert_eq!(value["keypair"]["public_key"], serde_json::json!(keypair.public_key.as_bytes()));
+    assert_eq!(value["peer"], serde_json::json!(peer.as_bytes()));
+
+    let stored: Stored = serde_json::from_value(value).expect("Should deserialize");
+    assert_eq!(stored.keypair, keypair);
+    assert_eq!(stored.peer, peer);
+}
+
+#[test]
+fn test_public_key_conditional_select() {
+    use dverse_identity::PublicKey;
+
+    let a = KeyPair::generate().expect("Should generate keypair").public_key;
+    let b = KeyPair::generate().expect("Should generate keypair").public_key;
+
+    let first = PublicKey::conditional_select(&a, &b, subtle::Choice::from(0)).expect("Should select");
+    let second = PublicKey::conditional_select(&a, &b, subtle::Choice::from(1)).expect("Should select");
+    assert_eq!(first, a);
+    assert_eq!(second, b);
+
+    let short = PublicKey::from_bytes(vec![0u8; 16]);
+    assert!(matches!(
+        PublicKey::conditional_select(&a, &short, subtle::Choice::from(0)),
+        Err(IdentityError::InvalidKey(_))
+    ));
+}
+
+#[test]
+fn test_did_with_null_or_trailing_newline_is_rejected() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let with_newline = format!("{}\n", did);
+    let (head, tail) = did.as_str().split_at(20);
+    let with_null = format!("{}\0{}", head, tail);
+
+    for malformed in [&with_newline, &with_null, &format!(" {}", did), &format!("{}\t", did)] {
+        assert!(matches!(Did::validate_syntax(malformed), Err(IdentityError::InvalidDidFormat(_))));
+    }
+    assert!(matches!(Did::from(with_null.as_str()).to_public_key(), Err(IdentityError::InvalidDidFormat(_))));
+}
+
+#[test]
+fn test_did_parsers_trim_surrounding_whitespace() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    for padded in [format!(" {}\n", did), format!("\t{}\r\n", did), format!("{}  ", did)] {
+        let padded = Did::from(padded.as_str());
+        assert_eq!(padded.to_public_key().expect("Should decode after trimming"), keypair.public_key);
+        assert_eq!(padded.parse().expect("Should parse after trimming").method, "dverse");
+        let document = padded.resolve_local().expect("Should resolve after trimming");
+        assert_eq!(document.id, did.as_str());
+        assert!(padded.same_key_any_network(&did).expect("Should compare after trimming"));
+    }
+}
+
+#[test]
+fn test_did_parsers_reject_internal_whitespace() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let (head, tail) = did.as_str().split_at(20);
+
+    for separator in [" ", "\n", "\t"] {
+        let broken = Did::from(format!(" {}{}{} ", head, separator, tail));
+        assert!(matches!(broken.to_public_key(), Err(IdentityError::InvalidDidFormat(_))));
+        assert!(matches!(broken.parse(), Err(IdentityError::InvalidDidFormat(_))));
+    }
+}
+
+#[test]
+fn test_key_and_signature_length_errors_are_distinct() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.sign(b"length check").expect("Should sign");
+
+    let corrupt_key = dverse_identity::PublicKey::from_bytes(keypair.public_key.as_bytes()[..31].to_vec());
+    let result = dverse_identity::verify(&corrupt_key, b"length check", &signature);
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+
+    let result = keypair.verify(b"length check", &signature[..63]);
+    assert!(matches!(result, Err(IdentityError::InvalidSignatureLength(_))));
+
+    let result = keypair.verify_with_policy(b"length check", &signature[..63], VerifyPolicy::Legacy);
+    assert!(matches!(result, Err(IdentityError::InvalidSignatureLength(_))));
+}
+
+#[test]
+fn test_did_with_known_but_unsupported_multibase_is_reported_clearly() {
+    // 'p' is the proquint prefix in the multibase table, which the multibase crate lacks.
+    let err = Did::from("did:dverse:plusab-dabab").to_public_key().expect_err("Should fail to decode");
+    match err {
+        IdentityError::UnsupportedMultibase(msg) => {
+            assert!(msg.contains("'p'"), "Message should name the prefix: {}", msg);
+            assert!(msg.contains("proquint"), "Message should name the base: {}", msg);
+        }
+        other => panic!("Expected UnsupportedMultibase, got {:?}", other),
+    }
+
+    // Prefixes outside the multibase table are still plain multibase errors.
+    let err = Did::from("did:dverse:xABC").to_public_key().expect_err("Should fail to decode");
+    assert!(matches!(err, IdentityError::MultibaseError(_)));
+}
+
+#[test]
+fn test_decryption_and_authentication_errors_display() {
+    let decryption = IdentityError::DecryptionError("wrong password".to_string());
+    assert_eq!(decryption.to_string(), "Decryption Error: wrong password");
+
+    let authentication = IdentityError::AuthenticationFailed("tag mismatch".to_string());
+    assert_eq!(authentication.to_string(), "Authentication Failed: tag mismatch");
+    assert!(std::error::Error::source(&authentication).is_none());
+}
+
+#[test]
+fn test_identity_error_exposes_source() {
+    use std::error::Error;
+
+    let multibase_error = Did::from("did:dverse:z0OIl").to_public_key().expect_err("Should fail to decode");
+    assert!(matches!(multibase_error, IdentityError::MultibaseError(_)));
+    assert!(multibase_error.source().is_some());
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let mut signature = keypair.sign(b"source").expect("Should sign");
+    signature[0] ^= 1;
+    let dalek_error = keypair.verify(b"source", &signature).expect_err("Should fail to verify");
+    assert!(matches!(dalek_error, IdentityError::DalekError(_)));
+    assert!(dalek_error.source().is_some());
+
+    assert!(IdentityError::KeyGenerationError("no entropy".to_string()).source().is_none());
+
+    fn boxed() -> Result<(), Box<dyn Error>> {
+        Did::from("not a did").to_public_key()?;
+        Ok(())
+    }
+    assert!(boxed().is_err());
+}
+
+#[test]
+fn test_hex_and_base64_round_trip_keys() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let public_key = &keypair.public_key;
+
+    assert_eq!(public_key.to_hex().len(), 64);
+    assert_eq!(&dverse_identity::PublicKey::from_hex(&public_key.to_hex()).unwrap(), public_key);
+    assert_eq!(&dverse_identity::PublicKey::from_base64(&public_key.to_base64()).unwrap(), public_key);
+    assert_eq!(PrivateKey::from_hex(&keypair.private_key.to_hex()).unwrap(), keypair.private_key);
+    assert_eq!(PrivateKey::from_base64(&keypair.private_key.to_base64()).unwrap(), keypair.private_key);
+
+    let short = &public_key.to_hex()[..62];
+    assert!(matches!(dverse_identity::PublicKey::from_hex(short), Err(IdentityError::InvalidKey(_))));
+    assert!(matches!(PrivateKey::from_base64("AAAA"), Err(IdentityError::InvalidKey(_))));
+    assert!(matches!(dverse_identity::PublicKey::from_hex("zz"), Err(IdentityError::DecodingError(_))));
+
+    // Uppercase hex is accepted; output stays lowercase.
+    let upper = public_key.to_hex().to_uppercase();
+    assert_eq!(&dverse_identity::PublicKey::from_hex(&upper).unwrap(), public_key);
+    assert_eq!(PrivateKey::from_hex(&keypair.private_key.to_hex().to_uppercase()).unwrap(), keypair.private_key);
+}
+
+#[test]
+fn test_signature_round_trips_through_hex_json_field() {
+    use dverse_identity::Signature;
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.private_key.try_sign(b"payload").expect("Should sign");
+
+    let json = serde_json::json!({ "message": "payload", "signature": signature.to_hex() }).to_string();
+    let value: serde_json::Value = serde_json::from_str(&json).expect("Should parse JSON");
+    let parsed = Signature::from_hex(value["signature"].as_str().unwrap()).expect("Should decode signature");
+
+    assert_eq!(parsed, signature);
+    keypair.verify(b"payload", parsed.as_ref()).expect("Round-tripped signature should verify");
+    assert_eq!(Signature::from_base64(&signature.to_base64()).unwrap(), signature);
+    assert!(matches!(Signature::from_hex("abcd"), Err(IdentityError::InvalidSignatureLength(_))));
+    assert_eq!(Signature::from_hex(&signature.to_hex().to_uppercase()).unwrap(), signature);
+}
+
+#[test]
+fn test_keypair_from_seed_matches_rfc8032_vector() {
+    // RFC 8032 section 7.1, test 1.
+    let seed = PrivateKey::from_hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+        .expect("Should decode seed");
+    let seed: [u8; 32] = seed.as_bytes().try_into().unwrap();
+
+    let keypair = KeyPair::from_seed(&seed).expect("Should build keypair from seed");
+    assert_eq!(keypair.public_key.to_hex(), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    assert_eq!(keypair, KeyPair::from_seed(&seed).expect("Should build keypair from seed"));
+}
+
+#[test]
+fn test_keypair_from_private_key_recomputes_public_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let rebuilt = KeyPair::from_private_key(&keypair.private_key).expect("Should rebuild keypair");
+    assert_eq!(rebuilt, keypair);
+
+    let truncated = PrivateKey::from_bytes(keypair.private_key.as_bytes()[..31].to_vec());
+    assert!(matches!(KeyPair::from_private_key(&truncated), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_did_parse_components() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let method_ref = Did::from(format!("{}#keys-1", did));
+    let components = method_ref.parse().expect("Should parse verification method reference");
+    assert_eq!(components.method, "dverse");
+    assert_eq!(components.method_specific_id, did.as_str().trim_start_matches("did:dverse:"));
+    assert_eq!(components.path, None);
+    assert_eq!(components.query, None);
+    assert_eq!(components.fragment.as_deref(), Some("keys-1"));
+
+    let url = Did::from("did:web:example.com:user/profiles/1?service=files&v=2#main");
+    let components = url.parse().expect("Should parse DID URL");
+    assert_eq!(components.method, "web");
+    assert_eq!(components.method_specific_id, "example.com:user");
+    assert_eq!(components.path.as_deref(), Some("/profiles/1"));
+    assert_eq!(components.query.as_deref(), Some("service=files&v=2"));
+    assert_eq!(components.fragment.as_deref(), Some("main"));
+
+    assert!(matches!(Did::from("dverse:abc").parse(), Err(IdentityError::InvalidDidFormat(_))));
+    assert!(matches!(Did::from("did::abc").parse(), Err(IdentityError::InvalidDidFormat(_))));
+}
+
+#[test]
+fn test_verify_hex_inputs() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.private_key.try_sign(b"cli").expect("Should sign");
+    let public_hex = keypair.public_key.to_hex();
+    let message_hex = "636c69"; // "cli"
+
+    dverse_identity::verify_hex(&public_hex, message_hex, &signature.to_hex()).expect("Hex inputs should verify");
+    dverse_identity::verify_hex(&public_hex.to_uppercase(), "636C69", &signature.to_hex().to_uppercase())
+        .expect("Uppercase hex inputs should verify");
+    assert!(dverse_identity::verify_hex(&public_hex, "636c6a", &signature.to_hex()).is_err());
+
+    let result = dverse_identity::verify_hex(&public_hex, "not hex", &signature.to_hex());
+    assert!(matches!(result, Err(IdentityError::DecodingError(_))));
+    let result = dverse_identity::verify_hex(&public_hex[..60], message_hex, &signature.to_hex());
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+    let result = dverse_identity::verify_hex(&public_hex, message_hex, &signature.to_hex()[..126]);
+    assert!(matches!(result, Err(IdentityError::InvalidSignatureLength(_))));
+}
+
+#[test]
+fn test_did_config_base64url_round_trips() {
+    use dverse_identity::DidConfig;
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let config = DidConfig { method: "fork".to_string(), base: multibase::Base::Base64Url };
+
+    let did = Did::from_public_key_with_config(&keypair.public_key, &config).expect("Should derive DID");
+    assert!(did.as_str().starts_with("did:fork:u"));
+    assert_eq!(did.to_public_key_with_config(&config).expect("Should decode key"), keypair.public_key);
+    assert!(did.to_public_key_with_config(&DidConfig::default()).is_err());
+
+    // Decoding accepts any multibase, whatever base the config builds with.
+    let base58_fork = DidConfig { base: multibase::Base::Base58Btc, ..config.clone() };
+    assert_eq!(did.to_public_key_with_config(&base58_fork).expect("Should decode key"), keypair.public_key);
+
+    // The default method also accepts base64url-encoded identifiers.
+    let dverse_config = DidConfig { base: multibase::Base::Base64Url, ..DidConfig::default() };
+    let dverse_did = Did::from_public_key_with_config(&keypair.public_key, &dverse_config).expect("Should derive DID");
+    assert!(dverse_did.as_str().starts_with("did:dverse:u"));
+    assert_eq!(dverse_did.to_public_key().expect("Should decode key"), keypair.public_key);
+    let handwritten = Did::from(format!(
+        "did:dverse:{}",
+        multibase::encode(multibase::Base::Base64Url, [[0xed, 0x01].as_slice(), keypair.public_key.as_bytes()].concat())
+    ));
+    assert_eq!(handwritten, dverse_did);
+    assert_eq!(
+        Did::from_public_key_with_config(&keypair.public_key, &DidConfig::default()).unwrap(),
+        Did::from_public_key(&keypair.public_key).unwrap()
+    );
+}
+
+#[test]
+fn test_prehashed_and_plain_signatures_are_not_interchangeable() {
+    use sha2::{Digest, Sha512};
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = b"large file contents";
+    let prehash = || {
+        let mut hasher = Sha512::new();
+        hasher.update(&message[..5]);
+        hasher.update(&message[5..]);
+        hasher
+    };
+
+    let prehashed = keypair.sign_prehashed(prehash(), Some(b"files")).expect("Should sign prehashed");
+    keypair
+        .verify_prehashed(prehash(), Some(b"files"), &prehashed)
+        .expect("Prehashed signature should verify");
+    assert!(keypair.verify_prehashed(prehash(), None, &prehashed).is_err());
+    assert!(keypair.verify(message, &prehashed).is_err());
+
+    let plain = keypair.sign(message).expect("Should sign");
+    assert!(keypair.verify_prehashed(prehash(), None, &plain).is_err());
+
+    let chunked = keypair.sign_chunks([&message[..]]).expect("Should sign chunks");
+    keypair.verify_prehashed(prehash(), None, &chunked).expect("Chunked signature is Ed25519ph without context");
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_public_types_are_send_and_sync() {
+    assert_send_sync::<KeyPair>();
+    assert_send_sync::<dverse_identity::PublicKey>();
+    assert_send_sync::<PrivateKey>();
+    assert_send_sync::<Did>();
+    assert_send_sync::<IdentityError>();
+    assert_send_sync::<dverse_identity::Signature>();
+    assert_send_sync::<dverse_identity::SignedMessage>();
+    assert_send_sync::<dverse_identity::VersionedSignature>();
+    assert_send_sync::<dverse_identity::IdentityAgent>();
+    assert_send_sync::<dverse_identity::DidDocument>();
+    assert_send_sync::<dverse_identity::InMemoryNonceStore>();
+    assert_send_sync::<dverse_identity::capability::CapabilityToken>();
+    assert_send_sync::<dverse_identity::frost::KeyShare>();
+}
+
+#[test]
+fn test_did_binary_roundtrip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let binary = did.to_binary().expect("Should encode binary identity");
+    assert_eq!(&binary[..2], &[0xed, 0x01]);
+    assert_eq!(&binary[2..], keypair.public_key.as_bytes());
+    assert_eq!(Did::from_binary(&binary).expect("Should decode binary identity"), did);
+}
+
+#[test]
+fn test_did_from_binary_rejects_wrong_multicodec() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let mut binary = did.to_binary().unwrap();
+    binary[0] = 0xec; // X25519 multicodec
+
+    let result = Did::from_binary(&binary);
+    assert!(matches!(result, Err(IdentityError::UnsupportedMulticodec(_))));
+}
+
+#[test]
+fn test_did_try_from_binary_slice() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let binary = did.to_binary().expect("Should encode binary identity");
+
+    assert_eq!(Did::try_from(&binary[..]).expect("Should decode binary identity"), did);
+    assert!(matches!(Did::try_from(&binary[..33]), Err(IdentityError::ArrayConversionError(_))));
+
+    let mut wrong_codec = binary;
+    wrong_codec[0] = 0xec;
+    assert!(matches!(Did::try_from(&wrong_codec[..]), Err(IdentityError::UnsupportedMulticodec(_))));
+}
+
+#[test]
+fn test_register_custom_multicodec() {
+    use dverse_identity::{register_multicodec, KeyType};
+
+    // 0x1309 is not an assigned multicodec; use it as a private experimental key type.
+    let code = [0x89, 0x26];
+    let key_type = KeyType::Custom("experimental-lattice".to_string());
+    let key_bytes = vec![0x42; 48];
+    let did = Did::from(format!(
+        "did:dverse:{}",
+        multibase::encode(multibase::Base::Base58Btc, [code.as_slice(), &key_bytes].concat())
+    ));
+
+    let result = did.to_public_key();
+    assert!(matches!(result, Err(IdentityError::UnsupportedMulticodec(_))));
+
+    register_multicodec(&code, key_type.clone()).expect("Should register custom multicodec");
+    register_multicodec(&code, key_type.clone()).expect("Re-registering the same type is a no-op");
+
+    assert_eq!(did.key_type().expect("Should recognize registered code"), key_type);
+    let (typed, public_key) = did.to_typed_public_key().expect("Should decode registered key");
+    assert_eq!(typed, key_type);
+    assert_eq!(public_key.as_bytes(), key_bytes.as_slice());
+    assert!(matches!(did.to_public_key(), Err(IdentityError::UnsupportedMulticodec(_))));
+
+    let ed25519_did = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+    assert_eq!(ed25519_did.key_type().unwrap(), KeyType::Ed25519);
+}
+
+#[test]
+fn test_custom_multicodec_did_cannot_verify_ed25519_signature() {
+    use dverse_identity::{register_multicodec, KeyType};
+
+    let code = [0x8b, 0x26];
+    register_multicodec(&code, KeyType::Custom("not-ed25519".to_string())).expect("Should register custom multicodec");
+
+    // The same 32 bytes as a real Ed25519 key, but tagged with the custom code.
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from(format!(
+        "did:dverse:{}",
+        multibase::encode(multibase::Base::Base58Btc, [code.as_slice(), keypair.public_key.as_bytes()].concat())
+    ));
+    let signature = keypair.sign(b"message").expect("Should sign");
+
+    assert!(matches!(did.verify(b"message", &signature), Err(IdentityError::UnsupportedMulticodec(_))));
+    assert!(matches!(did.to_binary(), Err(IdentityError::UnsupportedMulticodec(_))));
+    assert!(matches!(did.to_did_key(), Err(IdentityError::UnsupportedMulticodec(_))));
+    assert!(matches!(
+        dverse_identity::CompactDidStore::new().insert(&did),
+        Err(IdentityError::UnsupportedMulticodec(_))
+    ));
+}
+
+#[test]
+fn test_register_multicodec_cannot_override_built_ins() {
+    use dverse_identity::{register_multicodec, KeyType};
+
+    let result = register_multicodec(&[0xed, 0x01], KeyType::Custom("shadow".to_string()));
+    assert!(matches!(result, Err(IdentityError::UnsupportedMulticodec(_))));
+
+    register_multicodec(&[0x8a, 0x26], KeyType::Custom("first".to_string())).unwrap();
+    let result = register_multicodec(&[0x8a, 0x26], KeyType::Custom("second".to_string()));
+    assert!(matches!(result, Err(IdentityError::UnsupportedMulticodec(_))));
+}
+
+#[test]
+fn test_public_key_from_compressed_edwards_y_accepts_valid_point() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let bytes: [u8; 32] = keypair.public_key.as_bytes().try_into().unwrap();
+
+    let public_key = dverse_identity::PublicKey::from_compressed_edwards_y(&bytes).expect("Valid point should be accepted");
+    assert_eq!(public_key, keypair.public_key);
+}
+
+#[test]
+fn test_public_key_from_compressed_edwards_y_rejects_non_canonical() {
+    // y = p + 1 (p = 2^255 - 19) decodes to the same point as y = 1 but is not canonical.
+    let mut non_canonical = [0xff; 32];
+    non_canonical[0] = 0xee;
+    non_canonical[31] = 0x7f;
+    let result = dverse_identity::PublicKey::from_compressed_edwards_y(&non_canonical);
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+
+    // y = 2 has no corresponding x on the curve.
+    let mut off_curve = [0u8; 32];
+    off_curve[0] = 2;
+    let result = dverse_identity::PublicKey::from_compressed_edwards_y(&off_curve);
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_did_as_json_map_key_roundtrip() {
+    use std::collections::HashMap;
+
+    let mut balances: HashMap<Did, u32> = HashMap::new();
+    for amount in [10u32, 20, 30] {
+        let did = Did::from_public_key(&KeyPair::generate().unwrap().public_key).unwrap();
+        balances.insert(did, amount);
+    }
+
+    let json = serde_json::to_string(&balances).expect("Should serialize DID-keyed map");
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let object = value.as_object().expect("DID-keyed map should serialize to a JSON object");
+    for (did, amount) in &balances {
+        assert_eq!(object.get(did.as_str()), Some(&serde_json::json!(amount)));
+    }
+
+    let decoded: HashMap<Did, u32> = serde_json::from_str(&json).expect("Should deserialize DID-keyed map");
+    assert_eq!(decoded, balances);
+}
+
+#[test]
+fn test_dids_and_public_keys_iterate_sorted_in_btree_collections() {
+    use std::collections::BTreeSet;
+
+    let keys: Vec<_> = (1u8..=6)
+        .map(|seed| KeyPair::from_seed(&[seed; 32]).expect("Should build keypair").public_key)
+        .collect();
+    let dids: Vec<Did> = keys.iter().map(|key| Did::from_public_key(key).expect("Should derive DID")).collect();
+
+    let forward: BTreeSet<Did> = dids.iter().cloned().collect();
+    let reverse: BTreeSet<Did> = dids.iter().rev().cloned().collect();
+    let order: Vec<&str> = forward.iter().map(Did::as_str).collect();
+    assert_eq!(order, reverse.iter().map(Did::as_str).collect::<Vec<_>>());
+
+    let mut expected: Vec<&str> = dids.iter().map(Did::as_str).collect();
+    expected.sort();
+    assert_eq!(order, expected);
+
+    let sorted_keys: Vec<_> = keys.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+    let mut expected_keys: Vec<&[u8]> = keys.iter().map(|key| key.as_bytes()).collect();
+    expected_keys.sort();
+    assert_eq!(sorted_keys.iter().map(|key| key.as_bytes()).collect::<Vec<_>>(), expected_keys);
+}
+
+#[test]
+fn test_available_encodings_resolve_to_same_key() {
+    use dverse_identity::DidConfig;
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let encodings = did.available_encodings().expect("Should list encodings");
+    assert_eq!(encodings[0], (multibase::Base::Base58Btc, did.clone()));
+    assert!(encodings.len() > 1);
+    for (base, encoded) in &encodings {
+        Did::validate_syntax(encoded.as_str()).expect("Every encoding should be a valid DID");
+        assert_eq!(encoded.as_str().as_bytes()[11], base.code() as u8);
+        assert_eq!(encoded.to_public_key().expect("Should decode re-encoded DID"), keypair.public_key);
+        assert_eq!(encoded.reencode(multibase::Base::Base58Btc).expect("Should re-encode back"), did);
+    }
+
+    // Other methods keep their own method name.
+    let config = DidConfig { method: "fork".to_string(), base: multibase::Base::Base64Url };
+    let fork = Did::from_public_key_with_config(&keypair.public_key, &config).expect("Should derive DID");
+    for (_, encoded) in fork.available_encodings().expect("Should list encodings") {
+        assert!(encoded.as_str().starts_with("did:fork:"));
+        assert_eq!(encoded.to_public_key_with_config(&config).expect("Should decode re-encoded DID"), keypair.public_key);
+    }
+}
+
+#[test]
+fn test_reencode_keeps_key_agreement_and_rejects_unsafe_bases() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_keypair_with_encryption(&keypair).expect("Should derive dual-key DID");
+
+    let base64url = did.reencode(multibase::Base::Base64Url).expect("Should re-encode");
+    assert_eq!(base64url.key_agreement_key().expect("Should keep X25519 key"), did.key_agreement_key().expect("Should decode"));
+    assert_eq!(base64url.reencode(multibase::Base::Base58Btc).expect("Should re-encode back"), did);
+
+    assert!(matches!(did.reencode(multibase::Base::Base64Pad), Err(IdentityError::InvalidDidFormat(_))));
+}
+
+#[test]
+fn test_private_key_equality_is_constant_time_and_correct() {
+    use subtle::ConstantTimeEq;
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let same = PrivateKey::from_bytes(keypair.private_key.as_bytes().to_vec());
+    assert_eq!(same, keypair.private_key);
+    assert!(bool::from(same.ct_eq(&keypair.private_key)));
+
+    let mut flipped = keypair.private_key.as_bytes().to_vec();
+    flipped[31] ^= 1;
+    assert_ne!(PrivateKey::from_bytes(flipped), keypair.private_key);
+    assert_ne!(PrivateKey::from_bytes(keypair.private_key.as_bytes()[..31].to_vec()), keypair.private_key);
+    assert_eq!(keypair.clone(), keypair);
+}
+
+#[test]
+fn test_checked_bytes_round_trip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let bytes = keypair.to_bytes_checked();
+    assert_eq!(bytes.len(), 96);
+    assert_eq!(KeyPair::from_bytes_checked(&bytes).expect("Should load checked bytes"), keypair);
+}
+
+#[test]
+fn test_checked_bytes_detect_corruption() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let bytes = keypair.to_bytes_checked();
+
+    for index in [0, 31, 32, 63, 64, 95] {
+        let mut corrupt = bytes.clone();
+        corrupt[index] ^= 0x01;
+        assert!(matches!(KeyPair::from_bytes_checked(&corrupt), Err(IdentityError::DecodingError(_))));
+    }
+    assert!(matches!(KeyPair::from_bytes_checked(&bytes[..95]), Err(IdentityError::DecodingError(_))));
+}
+
+#[test]
+fn test_did_key_round_trip_recovers_same_public_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let did_key = did.to_did_key().expect("Should convert to did:key");
+    assert!(did_key.as_str().starts_with("did:key:z6Mk"));
+    assert_eq!(
+        did_key.as_str().strip_prefix("did:key:"),
+        did.as_str().strip_prefix("did:dverse:"),
+        "Encodings should be identical apart from the method"
+    );
+    assert_eq!(Did::from_did_key(&did_key).expect("Should parse did:key"), keypair.public_key);
+    assert_eq!(did.to_public_key().expect("Should parse did:dverse"), keypair.public_key);
+}
+
+#[test]
+fn test_from_did_key_rejects_other_methods() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    assert!(matches!(Did::from_did_key(&did), Err(IdentityError::InvalidDidFormat(_))));
+
+    let dual = Did::from_keypair_with_encryption(&keypair).expect("Should derive dual-key DID");
+    let did_key = dual.to_did_key().expect("Should convert to did:key");
+    assert_eq!(did_key, did.to_did_key().expect("Should convert to did:key"));
+}
+
+#[test]
+fn test_private_key_seed_reproduces_keypair() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let seed = keypair.private_key.seed().expect("Should expose seed");
+    assert_eq!(&seed[..], keypair.private_key.as_bytes());
+    assert_eq!(KeyPair::from_seed(&seed).expect("Should rebuild from seed"), keypair);
+
+    let truncated = PrivateKey::from_bytes(keypair.private_key.as_bytes()[..16].to_vec());
+    assert!(matches!(truncated.seed(), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_public_key_thumbprint_and_fingerprint() {
+    // RFC 8032 test 1 public key.
+    let public_key = dverse_identity::PublicKey::from_hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        .expect("Should parse public key");
+    assert_eq!(public_key.thumbprint(), "3HhGPB6ht33n51YFaocqBtGePb3xqT4VgnjYbd81eeZW");
+    assert_eq!(public_key.fingerprint(), "21:fe:31:df:a1:54:a2:61");
+
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let other = KeyPair::generate().expect("Should generate keypair");
+    assert_eq!(keypair.public_key.thumbprint(), keypair.public_key.clone().thumbprint());
+    assert_eq!(keypair.public_key.fingerprint(), keypair.public_key.fingerprint());
+    assert_ne!(keypair.public_key.thumbprint(), other.public_key.thumbprint());
+    assert_ne!(keypair.public_key.fingerprint(), other.public_key.fingerprint());
+}
@@ -0,0 +1,91 @@
+use dverse_identity::{IdentityError, KeyPair, PrivateKey, PublicKey};
+use serde_json::json;
+
+#[test]
+fn test_jwk_round_trip_preserves_key_bytes() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+
+    let public_jwk = keypair.public_key.to_jwk().expect("Should export public JWK");
+    assert_eq!(public_jwk["kty"], "OKP");
+    assert_eq!(public_jwk["crv"], "Ed25519");
+    assert!(public_jwk.get("d").is_none());
+    let public_key = PublicKey::from_jwk(&public_jwk).expect("Should import public JWK");
+    assert_eq!(public_key.as_bytes(), keypair.public_key.as_bytes());
+
+    let private_jwk = keypair.private_key.to_jwk().expect("Should export private JWK");
+    assert_eq!(private_jwk["x"], public_jwk["x"]);
+    let private_key = PrivateKey::from_jwk(&private_jwk).expect("Should import private JWK");
+    assert_eq!(private_key.as_bytes(), keypair.private_key.as_bytes());
+    assert_eq!(PublicKey::from_jwk(&private_jwk).expect("Private JWK should carry x"), keypair.public_key);
+}
+
+#[test]
+fn test_jwk_rfc8037_example() {
+    // RFC 8037, appendix A.1 and A.2.
+    let jwk = json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "d": "nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+        "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo",
+    });
+    let private_key = PrivateKey::from_jwk(&jwk).expect("Should import RFC 8037 key");
+    assert_eq!(private_key.to_hex(), "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    assert_eq!(private_key.to_jwk().expect("Should export JWK"), jwk);
+}
+
+#[test]
+fn test_jwk_rejects_wrong_curve_type_and_padding() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let jwk = keypair.private_key.to_jwk().expect("Should export JWK");
+
+    for (member, value) in [("crv", "X25519"), ("crv", "P-256"), ("kty", "EC")] {
+        let mut wrong = jwk.clone();
+        wrong[member] = json!(value);
+        assert!(matches!(PublicKey::from_jwk(&wrong), Err(IdentityError::InvalidKey(_))));
+        assert!(matches!(PrivateKey::from_jwk(&wrong), Err(IdentityError::InvalidKey(_))));
+    }
+
+    let mut padded = jwk.clone();
+    padded["x"] = json!(format!("{}=", jwk["x"].as_str().expect("x should be a string")));
+    assert!(PublicKey::from_jwk(&padded).is_err());
+
+    let other = KeyPair::generate().expect("Should generate keypair");
+    let mut mismatched = jwk.clone();
+    mismatched["x"] = other.public_key.to_jwk().expect("Should export JWK")["x"].clone();
+    assert!(matches!(PrivateKey::from_jwk(&mismatched), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_jwk_proof_of_possession_verifies() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let jwk = keypair.public_key.to_jwk_with_pop(&keypair, b"server-nonce").expect("Should export JWK with PoP");
+    assert!(jwk["pop"]["signature"].is_string());
+
+    let public_key = PublicKey::verify_jwk_pop(&jwk, b"server-nonce").expect("PoP should verify");
+    assert_eq!(public_key, keypair.public_key);
+
+    assert!(matches!(PublicKey::verify_jwk_pop(&jwk, b"other-nonce"), Err(IdentityError::InvalidKey(_))));
+    let plain = keypair.public_key.to_jwk().expect("Should export JWK");
+    assert!(matches!(PublicKey::verify_jwk_pop(&plain, b"server-nonce"), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_jwk_proof_of_possession_by_other_key_is_rejected() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let attacker = KeyPair::generate().expect("Should generate keypair");
+
+    // The attacker cannot export someone else's key with their own proof.
+    assert!(matches!(
+        keypair.public_key.to_jwk_with_pop(&attacker, b"server-nonce"),
+        Err(IdentityError::InvalidKey(_))
+    ));
+
+    // Nor graft a proof made with their key onto the victim's JWK.
+    let mut jwk = keypair.public_key.to_jwk().expect("Should export JWK");
+    jwk["pop"] = attacker
+        .public_key
+        .to_jwk_with_pop(&attacker, b"server-nonce")
+        .expect("Should export JWK with PoP")["pop"]
+        .clone();
+    assert!(matches!(PublicKey::verify_jwk_pop(&jwk, b"server-nonce"), Err(IdentityError::DalekError(_))));
+}
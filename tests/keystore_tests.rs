@@ -0,0 +1,71 @@
+#![cfg(feature = "keystore")]
+
+use dverse_identity::{IdentityError, KeyPair, PrivateKey};
+
+#[test]
+fn test_encrypted_keystore_round_trip() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let json = keypair
+        .private_key
+        .to_encrypted_keystore("correct horse battery staple")
+        .expect("Should encrypt keystore");
+
+    let value: serde_json::Value = serde_json::from_str(&json).expect("Keystore should be JSON");
+    assert_eq!(value["kdf"]["algorithm"], "argon2id");
+    assert_eq!(value["cipher"]["algorithm"], "xchacha20poly1305");
+    for field in [&value["kdf"]["salt"], &value["cipher"]["nonce"], &value["ciphertext"]] {
+        assert!(field.is_string());
+    }
+    assert!(!json.contains(&multibase::Base::Base16Lower.encode(keypair.private_key.as_bytes())));
+
+    let decrypted = PrivateKey::from_encrypted_keystore(&json, "correct horse battery staple")
+        .expect("Should decrypt keystore");
+    assert_eq!(decrypted, keypair.private_key);
+
+    // A fresh salt and nonce each time.
+    let again = keypair.private_key.to_encrypted_keystore("correct horse battery staple").expect("Should encrypt");
+    assert_ne!(again, json);
+}
+
+#[test]
+fn test_encrypted_keystore_rejects_wrong_password() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let json = keypair.private_key.to_encrypted_keystore("hunter2").expect("Should encrypt keystore");
+
+    let result = PrivateKey::from_encrypted_keystore(&json, "hunter3");
+    assert!(matches!(result, Err(IdentityError::DecryptionError(_))));
+
+    let mut value: serde_json::Value = serde_json::from_str(&json).expect("Keystore should be JSON");
+    let ciphertext = value["ciphertext"].as_str().expect("Ciphertext should be a string").to_string();
+    let flipped = if ciphertext.starts_with('0') { "1" } else { "0" };
+    value["ciphertext"] = serde_json::json!(format!("{}{}", flipped, &ciphertext[1..]));
+    let result = PrivateKey::from_encrypted_keystore(&value.to_string(), "hunter2");
+    assert!(matches!(result, Err(IdentityError::DecryptionError(_))));
+
+    assert!(matches!(
+        PrivateKey::from_encrypted_keystore("not json", "hunter2"),
+        Err(IdentityError::DecodingError(_))
+    ));
+}
+
+#[test]
+fn test_encrypted_keystore_rejects_out_of_range_kdf_params() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let json = keypair.private_key.to_encrypted_keystore("hunter2").expect("Should encrypt keystore");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("Keystore should be JSON");
+
+    let cases = [
+        ("memoryKib", serde_json::json!(u32::MAX)),
+        ("memoryKib", serde_json::json!(8)),
+        ("iterations", serde_json::json!(1_000_000)),
+        ("iterations", serde_json::json!(1)),
+        ("parallelism", serde_json::json!(255)),
+        ("salt", serde_json::json!("00010203")),
+    ];
+    for (field, replacement) in cases {
+        let mut tampered = value.clone();
+        tampered["kdf"][field] = replacement;
+        let result = PrivateKey::from_encrypted_keystore(&tampered.to_string(), "hunter2");
+        assert!(matches!(result, Err(IdentityError::DecodingError(_))), "{} should be rejected", field);
+    }
+}
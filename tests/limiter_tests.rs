@@ -0,0 +1,68 @@
+use std::time::{Duration, SystemTime};
+
+use dverse_identity::{IdentityError, KeyPair, MockClock, VerificationLimiter};
+
+#[test]
+fn test_limiter_allows_burst_then_rate_limits() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.sign(b"hello").expect("Should sign");
+    let mut limiter = VerificationLimiter::new(2, 0.0).expect("Should build limiter");
+
+    limiter.try_verify(&keypair.public_key, b"hello", &signature).expect("First verify should run");
+    limiter.try_verify(&keypair.public_key, b"hello", &signature).expect("Second verify should run");
+
+    let result = limiter.try_verify(&keypair.public_key, b"hello", &signature);
+    assert!(matches!(result, Err(IdentityError::RateLimited(_))));
+}
+
+#[test]
+fn test_rate_limited_call_skips_verification() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let mut limiter = VerificationLimiter::new(1, 0.0).expect("Should build limiter");
+
+    // A malformed signature fails verification while the budget lasts...
+    let result = limiter.try_verify(&keypair.public_key, b"hello", b"garbage");
+    assert!(matches!(result, Err(IdentityError::InvalidSignatureLength(_))));
+
+    // ...but once it is spent the same input is rejected before it is ever parsed.
+    let result = limiter.try_verify(&keypair.public_key, b"hello", b"garbage");
+    assert!(matches!(result, Err(IdentityError::RateLimited(_))));
+    assert_eq!(limiter.available(), 0);
+}
+
+#[test]
+fn test_limiter_refills_over_time() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signature = keypair.sign(b"hello").expect("Should sign");
+    let clock = MockClock::new(SystemTime::UNIX_EPOCH);
+    let mut limiter = VerificationLimiter::with_clock(1, 50.0, clock.clone()).expect("Should build limiter");
+
+    limiter.try_verify(&keypair.public_key, b"hello", &signature).expect("First verify should run");
+    assert!(matches!(
+        limiter.try_verify(&keypair.public_key, b"hello", &signature),
+        Err(IdentityError::RateLimited(_))
+    ));
+
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(limiter.available(), 0);
+    clock.advance(Duration::from_millis(10));
+    limiter.try_verify(&keypair.public_key, b"hello", &signature).expect("Refilled token should allow verify");
+
+    // Setting the time back does not take tokens away or break the arithmetic.
+    clock.set(SystemTime::UNIX_EPOCH);
+    assert_eq!(limiter.available(), 0);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(limiter.available(), 1);
+}
+
+#[test]
+fn test_limiter_rejects_invalid_refill_rates() {
+    let clock = MockClock::new(SystemTime::UNIX_EPOCH);
+    for rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -1.0] {
+        assert!(matches!(VerificationLimiter::new(1, rate), Err(IdentityError::InvalidArgument(_))));
+        assert!(matches!(
+            VerificationLimiter::with_clock(1, rate, clock.clone()),
+            Err(IdentityError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+use dverse_identity::{ExtendedKeyPair, IdentityError, KeyPair};
+
+fn unhex(text: &str) -> Vec<u8> {
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).expect("Should be hex")).collect()
+}
+
+// BIP-39 English test vectors: (entropy byte, mnemonic, seed with passphrase "TREZOR").
+const VECTORS: [(u8, &str, &str); 4] = [
+    (
+        0x00,
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+        "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+    ),
+    (
+        0x7f,
+        "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title",
+        "bc09fca1804f7e69da93c2f2028eb238c227f2e9dda30cd63699232578480a4021b146ad717fbb7e451ce9eb835f43620bf5c514db0f8add49f5d121449d3e87",
+    ),
+    (
+        0x80,
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+        "c0c519bd0e91a2ed54357d9d1ebef6f5af218a153624cf4f2da911a0ed8f7a09e2ef61af0aca007096df430022f7a2b6fb91661a9589097069720d015e4e982f",
+    ),
+    (
+        0xff,
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+        "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+    ),
+];
+
+#[test]
+fn test_bip39_vectors_encode_and_decode() {
+    for (entropy_byte, phrase, _) in VECTORS {
+        let keypair = KeyPair::from_seed(&[entropy_byte; 32]).expect("Should build keypair");
+        assert_eq!(keypair.to_mnemonic().expect("Should encode mnemonic"), phrase);
+
+        let restored = KeyPair::from_mnemonic(phrase, "").expect("Should restore from mnemonic");
+        assert_eq!(restored.private_key.as_bytes(), [entropy_byte; 32]);
+        assert_eq!(restored.public_key, keypair.public_key);
+    }
+}
+
+#[test]
+fn test_passphrase_keys_match_bip39_vectors() {
+    for (_, phrase, seed) in VECTORS {
+        let restored = KeyPair::from_mnemonic(phrase, "TREZOR").expect("Should restore with passphrase");
+        let wallet = ExtendedKeyPair::from_seed(&unhex(seed)).expect("Should derive SLIP-0010 master key");
+        assert_eq!(&restored, wallet.keypair());
+    }
+
+    // Passphrases never change which phrases are accepted.
+    let twelve_words = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    assert!(matches!(KeyPair::from_mnemonic(twelve_words, "TREZOR"), Err(IdentityError::InvalidKey(_))));
+}
+
+#[test]
+fn test_mnemonic_round_trip_and_passphrase_changes_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let phrase = keypair.to_mnemonic().expect("Should encode mnemonic");
+    assert_eq!(phrase.split(' ').count(), 24);
+
+    let restored = KeyPair::from_mnemonic(&phrase, "").expect("Should restore from mnemonic");
+    assert_eq!(restored, keypair);
+    let signature = restored.sign(b"backup").expect("Should sign");
+    keypair.verify(b"backup", &signature).expect("Restored key should match original");
+
+    let hidden = KeyPair::from_mnemonic(&phrase, "correct horse").expect("Should restore with passphrase");
+    let other = KeyPair::from_mnemonic(&phrase, "battery staple").expect("Should restore with passphrase");
+    assert_ne!(hidden.public_key, keypair.public_key);
+    assert_ne!(hidden.public_key, other.public_key);
+}
+
+#[test]
+fn test_empty_passphrase_matches_no_passphrase_path() {
+    let keypair = KeyPair::from_seed(&[0x42; 32]).expect("Should build keypair");
+    let phrase = keypair.to_mnemonic().expect("Should encode mnemonic");
+
+    // The empty passphrase keeps the entropy as the key; only a real passphrase goes through PBKDF2.
+    let plain = KeyPair::from_mnemonic(&phrase, "").expect("Should restore without passphrase");
+    assert_eq!(plain.private_key.as_bytes(), [0x42; 32]);
+
+    let spaced = phrase.replace(' ', "  ");
+    let protected = KeyPair::from_mnemonic(&phrase, "TREZOR").expect("Should restore with passphrase");
+    let respaced = KeyPair::from_mnemonic(&spaced, "TREZOR").expect("Should restore with passphrase");
+    assert_ne!(protected, plain);
+    assert_eq!(protected, respaced);
+    assert_ne!(KeyPair::from_mnemonic(&phrase, "trezor").expect("Should restore with passphrase"), protected);
+}
+
+#[test]
+fn test_from_mnemonic_rejects_bad_phrases() {
+    let phrase = VECTORS[0].1;
+
+    let bad_checksum = phrase.replace(" art", " zoo");
+    let unknown_word = phrase.replacen("abandon", "abandonn", 1);
+    let too_short = "abandon abandon abandon";
+    let twelve_words = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    for bad in [bad_checksum.as_str(), unknown_word.as_str(), too_short, twelve_words] {
+        assert!(matches!(KeyPair::from_mnemonic(bad, ""), Err(IdentityError::InvalidKey(_))));
+    }
+}
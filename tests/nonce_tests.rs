@@ -0,0 +1,58 @@
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use dverse_identity::{IdentityError, InMemoryNonceStore, KeyPair, MockClock, NonceStore, SignatureDedup};
+
+#[test]
+fn test_nonce_consumed_twice_is_rejected() {
+    let mut store = InMemoryNonceStore::new(Duration::from_secs(60));
+    store.consume(b"challenge-1").expect("First use should succeed");
+    store.consume(b"challenge-2").expect("Different nonce should succeed");
+
+    let result = store.consume(b"challenge-1");
+    assert!(matches!(result, Err(IdentityError::ReplayDetected(_))));
+}
+
+#[test]
+fn test_expired_nonces_are_purged() {
+    let mut store = InMemoryNonceStore::new(Duration::from_millis(20));
+    store.consume(b"short-lived").expect("First use should succeed");
+    assert_eq!(store.len(), 1);
+
+    thread::sleep(Duration::from_millis(40));
+    store.purge_expired();
+    assert!(store.is_empty());
+
+    store.consume(b"short-lived").expect("Expired nonce should be forgotten");
+}
+
+#[test]
+fn test_nonce_ttl_with_mock_clock() {
+    let clock = MockClock::new(SystemTime::UNIX_EPOCH);
+    let mut store = InMemoryNonceStore::with_clock(Duration::from_secs(60), clock.clone());
+    store.consume(b"challenge").expect("First use should succeed");
+
+    clock.advance(Duration::from_secs(59));
+    assert!(matches!(store.consume(b"challenge"), Err(IdentityError::ReplayDetected(_))));
+
+    clock.advance(Duration::from_secs(1));
+    store.consume(b"challenge").expect("Nonce should expire exactly at the TTL");
+}
+
+#[test]
+fn test_signature_dedup_flags_repeated_signature() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let first = keypair.private_key.try_sign(b"order #1").expect("Should sign");
+    let second = keypair.private_key.try_sign(b"order #2").expect("Should sign");
+
+    let mut dedup = SignatureDedup::new();
+    assert!(!dedup.check_and_record(&first), "First submission is not a duplicate");
+    assert!(!dedup.check_and_record(&second));
+    assert!(dedup.check_and_record(&first), "Resubmission should be flagged");
+    assert!(dedup.contains(&second));
+    assert_eq!(dedup.len(), 2);
+
+    dedup.clear();
+    assert!(dedup.is_empty());
+    assert!(!dedup.check_and_record(&first));
+}
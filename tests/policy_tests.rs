@@ -0,0 +1,89 @@
+use dverse_identity::{Canonicalization, HardwareSigner, KeyPair, SignatureDomain, SigningPolicy};
+
+fn full_policy() -> SigningPolicy {
+    SigningPolicy::new()
+        .domain(b"payments".to_vec())
+        .audience("did:dverse:z6MkVerifier")
+        .canonicalization(Canonicalization::Json)
+        .context(b"v1".to_vec())
+}
+
+#[test]
+fn test_policy_signature_verifies_only_under_identical_policy() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let message = br#"{"amount": 10, "to": "bob"}"#;
+
+    let policy = full_policy();
+    let signature = policy.signer(&keypair).sign(message).expect("Should sign under policy");
+    full_policy()
+        .verifier(&keypair.public_key)
+        .verify(message, &signature)
+        .expect("Identical policy should verify");
+
+    let variants = [
+        SigningPolicy::new(),
+        full_policy().domain(b"login".to_vec()),
+        full_policy().audience("did:dverse:z6MkSomeoneElse"),
+        full_policy().canonicalization(Canonicalization::Raw),
+        full_policy().context(b"v2".to_vec()),
+        SigningPolicy::new()
+            .domain(b"payments".to_vec())
+            .audience("did:dverse:z6MkVerifier")
+            .canonicalization(Canonicalization::Json),
+    ];
+    for variant in &variants {
+        assert_ne!(variant, &policy);
+        assert!(variant.verifier(&keypair.public_key).verify(message, &signature).is_err());
+    }
+    assert!(keypair.verify(message, &signature).is_err());
+}
+
+#[test]
+fn test_json_canonicalization_ignores_whitespace_and_key_order() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let policy = SigningPolicy::new().canonicalization(Canonicalization::Json);
+
+    let signature = policy
+        .signer(&keypair)
+        .sign(br#"{"b": [1, {"y": true, "x": null}], "a": "text"}"#)
+        .expect("Should sign JSON");
+    policy
+        .verifier(&keypair.public_key)
+        .verify(br#"{"a":"text","b":[1,{"x":null,"y":true}]}"#, &signature)
+        .expect("Reordered JSON should verify");
+    assert!(policy.verifier(&keypair.public_key).verify(br#"{"a":"text","b":[2]}"#, &signature).is_err());
+    assert!(policy.signer(&keypair).sign(b"not json").is_err());
+}
+
+#[test]
+fn test_policies_never_match_plain_or_domain_signing() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+
+    let empty = SigningPolicy::new().signer(&keypair).sign(b"plain").expect("Should sign");
+    assert!(keypair.verify(b"plain", &empty).is_err());
+
+    // An audience-only policy used to sign `len || audience || message` with no tag.
+    let audience = "did:dverse:z6MkVerifier";
+    let bound = [&(audience.len() as u64).to_be_bytes()[..], audience.as_bytes(), b"plain"].concat();
+    let audience_only = SigningPolicy::new().audience(audience).signer(&keypair).sign(b"plain").expect("Should sign");
+    assert!(keypair.verify(&bound, &audience_only).is_err());
+    assert!(SigningPolicy::new().verifier(&keypair.public_key).verify(&bound, &audience_only).is_err());
+
+    let domain = SigningPolicy::new().domain(b"payments".to_vec()).signer(&keypair).sign(b"plain").expect("Should sign");
+    assert!(keypair.verify_in_domain(SignatureDomain::Custom(b"payments"), b"plain", &domain).is_err());
+
+    // An empty domain or context is not the same as none.
+    let empty_domain = SigningPolicy::new().domain(Vec::new());
+    assert!(empty_domain.verifier(&keypair.public_key).verify(b"plain", &empty).is_err());
+}
+
+#[test]
+fn test_policy_signs_with_any_hardware_signer() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signer: &dyn HardwareSigner = &keypair;
+    let policy = SigningPolicy::new().audience("did:dverse:z6MkVerifier");
+
+    let signature = policy.signer(signer).sign(b"hello").expect("Should sign through trait object");
+    policy.verifier(&keypair.public_key).verify(b"hello", &signature).expect("Should verify");
+    assert_eq!(signature, policy.signer(&keypair).sign(b"hello").expect("Should sign"));
+}
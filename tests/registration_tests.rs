@@ -0,0 +1,64 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use dverse_identity::registration::{REGISTRATION_MAX_AGE, REGISTRATION_MAX_CLOCK_SKEW};
+use dverse_identity::{verify_registration, Did, IdentityError, KeyPair, MockClock, RegistrationStatement};
+
+fn start() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+}
+
+#[test]
+fn test_valid_registration_statement_returns_did() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let clock = MockClock::new(start());
+    let statement = keypair
+        .register_statement_with_clock(b"server-nonce-1", &clock)
+        .expect("Should create statement");
+
+    let did = verify_registration(&statement, b"server-nonce-1", start() + Duration::from_secs(5))
+        .expect("Statement should verify");
+    assert_eq!(did, Did::from_public_key(&keypair.public_key).expect("Should derive DID"));
+
+    // Survives a round trip through JSON, as it would over the wire.
+    let json = serde_json::to_string(&statement).expect("Should serialize statement");
+    let decoded: RegistrationStatement = serde_json::from_str(&json).expect("Should deserialize statement");
+    assert_eq!(verify_registration(&decoded, b"server-nonce-1", start()).expect("Should verify"), did);
+
+    let live = keypair.register_statement(b"live").expect("Should create statement");
+    verify_registration(&live, b"live", SystemTime::now()).expect("Fresh statement should verify");
+}
+
+#[test]
+fn test_registration_with_wrong_nonce_is_rejected() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let clock = MockClock::new(start());
+    let statement = keypair.register_statement_with_clock(b"nonce-a", &clock).expect("Should create statement");
+
+    let result = verify_registration(&statement, b"nonce-b", start());
+    assert!(matches!(result, Err(IdentityError::InvalidCredential(_))));
+
+    // Rewriting the nonce to match breaks the signature instead.
+    let mut tampered = statement.clone();
+    tampered.nonce = b"nonce-b".to_vec();
+    assert!(verify_registration(&tampered, b"nonce-b", start()).is_err());
+}
+
+#[test]
+fn test_expired_registration_statement_is_rejected() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let clock = MockClock::new(start());
+    let statement = keypair.register_statement_with_clock(b"nonce", &clock).expect("Should create statement");
+
+    verify_registration(&statement, b"nonce", start() + REGISTRATION_MAX_AGE).expect("Should verify at the limit");
+    let result = verify_registration(&statement, b"nonce", start() + REGISTRATION_MAX_AGE + Duration::from_secs(1));
+    assert!(matches!(result, Err(IdentityError::InvalidCredential(_))));
+
+    // A statement dated too far ahead of the server's clock is rejected too.
+    let early = start() - REGISTRATION_MAX_CLOCK_SKEW - Duration::from_secs(1);
+    assert!(matches!(verify_registration(&statement, b"nonce", early), Err(IdentityError::InvalidCredential(_))));
+
+    // Moving the timestamp forward to dodge expiry invalidates the signature.
+    let mut redated = statement.clone();
+    redated.timestamp += REGISTRATION_MAX_AGE.as_secs();
+    assert!(verify_registration(&redated, b"nonce", start() + REGISTRATION_MAX_AGE * 2).is_err());
+}
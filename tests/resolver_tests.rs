@@ -0,0 +1,144 @@
+use dverse_identity::resolver::{
+    keys_equal, verify_with_resolver, DID_LD_JSON_CONTENT_TYPE, ERROR_INVALID_DID, ERROR_METHOD_NOT_SUPPORTED,
+};
+use dverse_identity::{Did, DidResolver, IdentityError, KeyPair, LocalResolver};
+
+#[test]
+fn test_local_resolver_resolves_dverse_did() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let result = LocalResolver.resolve(&did);
+    assert_eq!(result.did_resolution_metadata.content_type.as_deref(), Some(DID_LD_JSON_CONTENT_TYPE));
+    assert_eq!(result.did_resolution_metadata.error, None);
+
+    let document = result.into_document().expect("Should contain a document");
+    assert_eq!(document.id, did.as_str());
+    assert_eq!(document.verification_method.len(), 1);
+    assert_eq!(document.authentication, vec![format!("{}#keys-1", did)]);
+}
+
+#[test]
+fn test_local_resolver_reports_error_codes() {
+    let invalid = LocalResolver.resolve(&Did::from("not a did"));
+    assert_eq!(invalid.did_resolution_metadata.error.as_deref(), Some(ERROR_INVALID_DID));
+    assert!(invalid.did_document.is_none());
+    assert!(matches!(invalid.into_document(), Err(IdentityError::ResolutionError(_))));
+
+    let unsupported = LocalResolver.resolve(&Did::from("did:web:example.com"));
+    assert_eq!(unsupported.did_resolution_metadata.error.as_deref(), Some(ERROR_METHOD_NOT_SUPPORTED));
+
+    let undecodable = LocalResolver.resolve(&Did::from("did:dverse:z6NABC"));
+    assert_eq!(undecodable.did_resolution_metadata.error.as_deref(), Some(ERROR_INVALID_DID));
+}
+
+#[test]
+fn test_local_resolver_handles_short_payload() {
+    // "z1" decodes to a single zero byte, shorter than any multicodec prefix.
+    let result = LocalResolver.resolve(&Did::from("did:dverse:z1"));
+    assert_eq!(result.did_resolution_metadata.error.as_deref(), Some(ERROR_INVALID_DID));
+}
+
+#[test]
+fn test_resolve_local_key_matches_to_public_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let document = did.resolve_local().expect("Should resolve locally");
+    assert_eq!(document, LocalResolver.resolve(&did).into_document().expect("Should contain a document"));
+
+    let method_did = Did::from(format!("did:dverse:{}", document.verification_method[0].public_key_multibase));
+    assert_eq!(
+        method_did.to_public_key().expect("Method key should decode"),
+        did.to_public_key().expect("DID key should decode")
+    );
+
+    assert!(matches!(Did::from("did:web:example.com").resolve_local(), Err(IdentityError::InvalidDidFormat(_))));
+}
+
+#[test]
+fn test_verify_with_resolver_end_to_end() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let signature = keypair.sign(b"federated hello").expect("Should sign");
+
+    verify_with_resolver(&LocalResolver, &did, b"federated hello", &signature).expect("Should verify via resolver");
+    assert!(verify_with_resolver(&LocalResolver, &did, b"tampered", &signature).is_err());
+
+    let other = Did::from_public_key(&KeyPair::generate().expect("Should generate keypair").public_key)
+        .expect("Should derive DID");
+    assert!(verify_with_resolver(&LocalResolver, &other, b"federated hello", &signature).is_err());
+    assert!(matches!(
+        verify_with_resolver(&LocalResolver, &Did::from("did:web:example.com"), b"federated hello", &signature),
+        Err(IdentityError::ResolutionError(_))
+    ));
+}
+
+#[test]
+fn test_to_did_document_round_trips_through_serde_json() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    let document = did.to_did_document().expect("Should build DID document");
+    let json = serde_json::to_string(&document).expect("Should serialize document");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("Should be valid JSON");
+
+    assert_eq!(value["@context"][0], "https://www.w3.org/ns/did/v1");
+    assert_eq!(value["id"], did.as_str());
+    assert_eq!(value["verificationMethod"][0]["id"], format!("{}#keys-1", did));
+    assert_eq!(value["verificationMethod"][0]["type"], "Ed25519VerificationKey2020");
+    assert!(value["verificationMethod"][0]["publicKeyMultibase"].as_str().unwrap().starts_with('z'));
+    assert_eq!(value["authentication"][0], format!("{}#keys-1", did));
+    assert_eq!(value["assertionMethod"][0], format!("{}#keys-1", did));
+
+    let parsed: dverse_identity::DidDocument = serde_json::from_str(&json).expect("Should parse document");
+    assert_eq!(parsed.id, did.as_str());
+    assert_eq!(parsed, document);
+}
+
+// Serves a fixed document for a `did:web` DID, standing in for an HTTPS fetch.
+struct StaticWebResolver {
+    document: dverse_identity::DidDocument,
+}
+
+impl DidResolver for StaticWebResolver {
+    fn resolve(&self, did: &Did) -> dverse_identity::ResolutionResult {
+        if did.as_str() == self.document.id {
+            dverse_identity::ResolutionResult::success(self.document.clone(), Default::default())
+        } else {
+            dverse_identity::ResolutionResult::error(dverse_identity::resolver::ERROR_NOT_FOUND)
+        }
+    }
+}
+
+#[test]
+fn test_keys_equal_across_methods() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let dverse_did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+    let encoded_key = dverse_did.as_str().trim_start_matches("did:dverse:");
+    let key_did = Did::from(format!("did:key:{}", encoded_key));
+
+    let web_did = Did::from("did:web:alice.example.com");
+    let mut document = dverse_did.to_did_document().expect("Should build document");
+    document.id = web_did.to_string();
+    let resolver = StaticWebResolver { document };
+
+    assert!(keys_equal(&dverse_did, &key_did, &resolver).expect("Should compare"));
+    assert!(keys_equal(&key_did, &web_did, &resolver).expect("Should compare"));
+
+    let other = Did::from_public_key(&KeyPair::generate().expect("Should generate keypair").public_key)
+        .expect("Should derive DID");
+    assert!(!keys_equal(&dverse_did, &other, &resolver).expect("Should compare"));
+    assert!(keys_equal(&dverse_did, &Did::from("did:web:unknown.example.com"), &resolver).is_err());
+}
+
+#[test]
+fn test_generate_with_document_matches_keypair() {
+    let (keypair, document) = KeyPair::generate_with_document().expect("Should generate keypair and document");
+    let did = Did::from_public_key(&keypair.public_key).expect("Should derive DID");
+
+    assert_eq!(document.id, did.as_str());
+    let method_did = Did::from(format!("did:dverse:{}", document.verification_method[0].public_key_multibase));
+    assert_eq!(method_did.to_public_key().expect("Method key should decode"), keypair.public_key);
+    assert_eq!(document.assertion_method, vec![document.verification_method[0].id.clone()]);
+}
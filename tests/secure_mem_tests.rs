@@ -0,0 +1,39 @@
+use dverse_identity::{KeyPair, PrivateKey};
+
+#[test]
+fn test_memory_locking_does_not_corrupt_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let original = keypair.private_key.as_bytes().to_vec();
+
+    // Locking is best effort, so only check the flag where it can never be set.
+    if !cfg!(feature = "secure-mem") {
+        assert!(!keypair.private_key.is_memory_locked());
+    }
+
+    let copy = keypair.clone();
+    assert_eq!(copy.private_key.as_bytes(), original.as_slice());
+    drop(copy);
+
+    // Unlocking the dropped copy must leave the original intact and usable.
+    assert_eq!(keypair.private_key.as_bytes(), original.as_slice());
+    let signature = keypair.sign(b"locked").expect("Should sign with locked key");
+    keypair.verify(b"locked", &signature).expect("Signature should verify");
+
+    let rebuilt = PrivateKey::from_bytes(original.clone());
+    assert_eq!(rebuilt, keypair.private_key);
+    let json = serde_json::to_string(&rebuilt).expect("Should serialize");
+    let parsed: PrivateKey = serde_json::from_str(&json).expect("Should deserialize");
+    assert_eq!(parsed.as_bytes(), original.as_slice());
+}
+
+
+#[test]
+fn test_debug_output_redacts_private_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let raw = format!("{:?}", keypair.private_key.as_bytes());
+    let inner = raw.trim_start_matches('[').trim_end_matches(']');
+
+    for output in [format!("{:?}", keypair), format!("{:?}", keypair.private_key), format!("{:#?}", keypair)] {
+        assert!(!output.contains(inner), "Debug output should not contain key bytes");
+    }
+}
@@ -0,0 +1,95 @@
+use dverse_identity::capability;
+use dverse_identity::{
+    Did, GuardedSigner, HardwareSigner, IdentityError, KeyPair, Profile, ProfileCredential, PublicKey, Result, SignatureDomain,
+};
+
+#[test]
+fn test_guarded_signer_is_unlimited_by_default() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signer = GuardedSigner::new(keypair.clone());
+    assert_eq!(signer.max_message_len(), None);
+
+    let message = vec![7u8; 1 << 20];
+    let signature = signer.sign(&message).expect("Unlimited signer should sign");
+    keypair.verify(&message, &signature).expect("Signature should verify");
+}
+
+#[test]
+fn test_guarded_signer_rejects_oversized_messages_but_not_streaming() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signer = GuardedSigner::new(keypair.clone()).with_max_message_len(16);
+
+    signer.sign(&[1u8; 16]).expect("Message at the limit should sign");
+    let result = signer.sign(&[1u8; 17]);
+    assert!(matches!(result, Err(IdentityError::MessageTooLarge(_))));
+
+    let payload = vec![1u8; 1024];
+    let chunks: Vec<&[u8]> = payload.chunks(100).collect();
+    let signature = signer.sign_chunks(chunks.iter().copied()).expect("Streaming signing should ignore the limit");
+    keypair.verify_chunks(chunks, &signature).expect("Streaming signature should verify");
+}
+
+// Stands in for a YubiKey/TPM: holds the key privately and counts signing requests.
+struct MockHardwareSigner {
+    device_key: KeyPair,
+    signatures: std::cell::Cell<usize>,
+}
+
+impl HardwareSigner for MockHardwareSigner {
+    fn public_key(&self) -> Result<PublicKey> {
+        Ok(self.device_key.public_key.clone())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.signatures.set(self.signatures.get() + 1);
+        self.device_key.sign(message)
+    }
+}
+
+fn mock_signer() -> MockHardwareSigner {
+    MockHardwareSigner { device_key: KeyPair::generate().expect("Should generate keypair"), signatures: std::cell::Cell::new(0) }
+}
+
+#[test]
+fn test_mock_hardware_signer_issues_credential() {
+    let signer = mock_signer();
+    let profile = Profile { display_name: "Hardware".to_string(), avatar_url: None, contact_endpoints: Vec::new() };
+
+    let credential = ProfileCredential::issue(&signer, profile).expect("Should issue credential");
+    credential.verify().expect("Hardware-signed credential should verify");
+    assert_eq!(signer.signatures.get(), 1);
+    assert_eq!(credential.issuer, Did::from_public_key(&signer.public_key().expect("Should read key")).expect("Should derive DID"));
+}
+
+#[test]
+fn test_mock_hardware_signer_answers_login_challenge_and_delegates() {
+    let signer = mock_signer();
+    let signature = SignatureDomain::Login.sign_with(&signer, b"challenge-nonce").expect("Should sign challenge");
+    signer
+        .device_key
+        .verify_in_domain(SignatureDomain::Login, b"challenge-nonce", &signature)
+        .expect("Challenge response should verify");
+
+    let issuer_did = Did::from_public_key(&signer.device_key.public_key).expect("Should derive DID");
+    let audience = Did::from_public_key(&KeyPair::generate().expect("Should generate keypair").public_key)
+        .expect("Should derive DID");
+    let token = capability::delegate(&signer, &issuer_did, &audience, Vec::new()).expect("Should delegate");
+    capability::verify_capability(&token, &issuer_did, "read", "storage://photos")
+        .expect("Hardware-signed capability should verify");
+}
+
+#[test]
+fn test_keypair_is_a_hardware_signer() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let signer: &dyn HardwareSigner = &keypair;
+    assert_eq!(signer.public_key().expect("Should read key"), keypair.public_key);
+    let signature = signer.sign(b"software").expect("Should sign");
+    keypair.verify(b"software", &signature).expect("Should verify");
+
+    let profile = Profile { display_name: "Boxed".to_string(), avatar_url: None, contact_endpoints: Vec::new() };
+    let boxed: Box<dyn HardwareSigner> = Box::new(keypair);
+    ProfileCredential::issue(boxed.as_ref(), profile)
+        .expect("Should issue through a trait object")
+        .verify()
+        .expect("Should verify");
+}
@@ -0,0 +1,36 @@
+use dverse_identity::{Did, IdentityError, KeyPair, X25519Public};
+
+#[test]
+fn test_alice_and_bob_derive_same_shared_secret() {
+    let alice = KeyPair::generate().expect("Should generate Alice");
+    let bob = KeyPair::generate().expect("Should generate Bob");
+
+    let (alice_secret, alice_public) = alice.to_x25519_keypair().expect("Should convert Alice");
+    let (bob_secret, bob_public) = bob.to_x25519_keypair().expect("Should convert Bob");
+
+    let alice_shared = alice_secret.diffie_hellman(&bob_public).expect("Alice should agree");
+    let bob_shared = bob_secret.diffie_hellman(&alice_public).expect("Bob should agree");
+    assert_eq!(alice_shared, bob_shared);
+
+    let eve = KeyPair::generate().expect("Should generate Eve");
+    let (eve_secret, _) = eve.to_x25519_keypair().expect("Should convert Eve");
+    assert_ne!(eve_secret.diffie_hellman(&bob_public).expect("Eve should agree"), alice_shared);
+}
+
+#[test]
+fn test_x25519_public_matches_did_key_agreement_key() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let (_, public) = keypair.to_x25519_keypair().expect("Should convert keypair");
+
+    let did = Did::from_keypair_with_encryption(&keypair).expect("Should derive dual-key DID");
+    assert_eq!(public.as_bytes(), &did.key_agreement_key().expect("DID should carry key agreement key"));
+}
+
+#[test]
+fn test_diffie_hellman_rejects_small_order_peer() {
+    let keypair = KeyPair::generate().expect("Should generate keypair");
+    let (secret, _) = keypair.to_x25519_keypair().expect("Should convert keypair");
+
+    let result = secret.diffie_hellman(&X25519Public::from_bytes([0u8; 32]));
+    assert!(matches!(result, Err(IdentityError::InvalidKey(_))));
+}